@@ -0,0 +1,18 @@
+// counts down to the release moment (data-release carries its utc offset). the page itself only changes on the next build
+for (const countdown of document.querySelectorAll("count-down")) {
+	const release = new Date(countdown.dataset.release);
+	const tick = () => {
+		const left = release - new Date();
+		if (left <= 0) {
+			countdown.textContent = "Any moment now";
+			return;
+		}
+		const days = Math.floor(left / 86400000);
+		const hours = Math.floor(left / 3600000) % 24;
+		const minutes = Math.floor(left / 60000) % 60;
+		const seconds = Math.floor(left / 1000) % 60;
+		countdown.textContent = days + "d " + hours + "h " + minutes + "m " + seconds + "s";
+		setTimeout(tick, 1000);
+	};
+	tick();
+}
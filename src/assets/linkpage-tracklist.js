@@ -0,0 +1,16 @@
+// tracklist timestamps seek the mix, and the track that's playing gets highlighted
+const audio = document.querySelector("audio");
+const seekers = [...document.querySelectorAll("[data-seek]")];
+for (const seeker of seekers) {
+	seeker.addEventListener("click", event => {
+		event.preventDefault();
+		audio.currentTime = Number(seeker.dataset.seek);
+		audio.play();
+	});
+}
+audio.addEventListener("timeupdate", () => {
+	const current = seekers.filter(seeker => Number(seeker.dataset.seek) <= audio.currentTime).pop();
+	for (const seeker of seekers) {
+		seeker.closest("tr").classList.toggle("playing", seeker === current);
+	}
+});
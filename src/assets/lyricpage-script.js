@@ -1,4 +1,4 @@
-/* let playSyncedButton = document.querySelector("form").appendChild(document.createElement("a"));
+let playSyncedButton = document.querySelector("form").appendChild(document.createElement("a"));
 playSyncedButton.innerText = "Show lyrics in sync with audio";
 playSyncedButton.style.marginLeft = "2rem"; //scuffed
 
@@ -22,6 +22,14 @@ playSyncedButton.onclick = function() {
 			};
 		});
 		let currentMoment = null; // Option<Moment>
+		// karaoke lines carry <l-w> children with their own data-start/data-end
+		const wordMoments = Array.from(document.querySelectorAll("l-w")).map(function(lw) {
+			return {
+				element: lw,
+				start: parseFloat(lw.getAttribute("data-start")) - aheadTime,
+				end: parseFloat(lw.getAttribute("data-end")) - aheadTime
+			};
+		});
 		
 		let apContainer = document.querySelector("form").appendChild(document.createElement("ap-container"));
 		let apPlayback = apContainer.appendChild(document.createElement("ap-playback"));
@@ -61,6 +69,12 @@ playSyncedButton.onclick = function() {
 			} else {
 				findNewMoment(); //bleh is there some way to make this faster
 			}
+			for (let wordMoment of wordMoments) {
+				const sung = audio.currentTime >= wordMoment.start;
+				const current = sung && audio.currentTime < wordMoment.end;
+				wordMoment.element.setAttribute("data-sung", sung ? "true" : "false");
+				wordMoment.element.setAttribute("data-current", current ? "true" : "false");
+			}
 		}, 50);
 		
 		function findNewMoment() {
@@ -71,6 +71,14 @@ pub fn inline_download_icon_svg() -> XmlNode {
 		)
 }
 
+// the little "E" next to explicit titles, on the home page and link pages
+pub fn explicit_badge() -> XmlNode {
+	XmlNode::new("e-b")
+		.with_attribute("title", "Explicit")
+		.with_attribute("aria-label", "Explicit")
+		.with_text("E")
+}
+
 // based on https://astronomy487.com/logo/assets/cmy-big.svg
 pub fn inline_logo_svg(cyan: &Color, magenta: &Color, yellow: &Color) -> XmlNode {
 	XmlNode::new("svg")
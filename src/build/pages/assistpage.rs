@@ -0,0 +1,81 @@
+// link pages for assists, at music.astronomy487.com/slug. much smaller than linkpage.rs since the release isn't ours
+
+use crate::build::{pages::linkpage, smartquotes, xml::XmlNode};
+use crate::globals;
+use crate::media::assist::Assist;
+
+pub fn make_assist_page(assist: &Assist) {
+	let canonical_url = format!("https://music.astronomy487.com/{}/", assist.slug);
+	let format_title = assist.format_title();
+	let description = format!(
+		"{} on {}, released {}",
+		assist.role.to_string(),
+		assist.titlable,
+		assist.released.to_display()
+	);
+	let artwork_url = format!("../artwork/{}.jpg", assist.artwork.name_without_slash);
+	let host = assist
+		.url
+		.trim_start_matches("https://")
+		.split('/')
+		.next()
+		.unwrap_or(&assist.url);
+
+	let destination_folder = globals::filezone()
+		.join("music.astronomy487.com")
+		.join(&assist.slug);
+	assert!(
+		!destination_folder.exists(),
+		"Directory {} already exists in music.astronomy487.com",
+		assist.slug
+	);
+	std::fs::create_dir(&destination_folder).unwrap_or_else(|_| {
+		panic!(
+			"Couldn't create directory {} in music.astronomy487.com",
+			destination_folder.display()
+		)
+	});
+
+	let head = linkpage::page_head(
+		&canonical_url,
+		&format_title,
+		&assist.palette,
+		&description,
+		&artwork_url
+	);
+
+	let body = XmlNode::new("body")
+		.maybe_with_attribute("class", assist.palette.palette_mode_as_css_class_name())
+		.with_child(
+			XmlNode::new("img")
+				.with_attribute("src", artwork_url)
+				.with_attribute("alt", smartquotes::smart_quotes(&assist.artwork.caption))
+		)
+		.with_child(XmlNode::new("h1").with_text(smartquotes::smart_quotes(&assist.titlable)))
+		.with_child(
+			XmlNode::new("table")
+				.with_attribute("class", "metadata")
+				.with_child(
+					XmlNode::new("tr")
+						.with_child(XmlNode::new("td").with_text(assist.released.to_display()))
+						.with_child(XmlNode::new("td").with_text(assist.role.to_string()))
+				)
+		)
+		.with_child(
+			XmlNode::new("link-set").with_child(
+				XmlNode::new("a")
+					.with_attribute("href", &assist.url)
+					.with_child(XmlNode::new("span").with_text(format!("Listen on {}", host)))
+			)
+		);
+
+	let html = XmlNode::new("html")
+		.with_attribute("lang", "en")
+		.with_child(head)
+		.with_child(body);
+	let index_html_location = destination_folder.join("index").with_extension("html");
+	let mut file = std::fs::File::create(&index_html_location)
+		.unwrap_or_else(|_| panic!("Couldn't create file {}", index_html_location.display()));
+	let _ = std::io::Write::write(&mut file, format!("<!DOCTYPE html>{}", html).as_bytes())
+		.unwrap_or_else(|_| panic!("Couldn't write to file {}", index_html_location.display()));
+}
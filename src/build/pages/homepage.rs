@@ -3,6 +3,7 @@ use crate::globals;
 use crate::media::{album::Album, assist::Assist, song::Song};
 use crate::types::color::Color;
 use crate::types::date::Date;
+use crate::types::releasetype::ReleaseType;
 
 const OG_DESCRIPTION: &str = "Astro (f.k.a. astronomy487) is an independent electronic dance music artist with a love for synthesizers and pop music.";
 
@@ -73,6 +74,9 @@ fn song_xml(song: &Song, for_album: Option<(&Album, &str)>, have_link: bool) ->
 							} else {
 								span.add_text(smartquotes::smart_quotes(&song.format_title_short()));
 							}
+							if song.explicit.is_explicit() {
+								span.add_child(icons::explicit_badge());
+							}
 							if is_hover {
 								// technically panics in the case Hover has no url, since the <span> will have direct text children by now. lol I LOVE AVOIDING TYPE-SAFE PATTERNS!!
 								span.add_child(
@@ -312,13 +316,16 @@ pub fn make_home_page(all_albums: &[Album], all_remixes: &[Song], all_assists: &
 			mobile_header
 		})
 		.with_child({
-			let mut entries: Vec<(EntryType, &Date, XmlNode)> = Vec::new();
-			for album in all_albums {
+			// the &str is what the type filter calls it: a release type, or "assist"
+			let mut entries: Vec<(EntryType, &Date, &str, XmlNode)> = Vec::new();
+			// a folded temporary single is just one of its album's singles now
+			for album in all_albums.iter().filter(|album| album.folded_into.is_none()) {
 				let main_songs = album.songs.iter().filter(|s| !s.bonus).collect::<Vec<_>>();
 				let bonus_songs = album.songs.iter().filter(|s| s.bonus).collect::<Vec<_>>();
 				entries.push((
 					EntryType::Album,
 					&album.released,
+					album.release_type.to_string(),
 					XmlNode::new("a-a")
 						.with_attribute("style", album.palette.style_text())
 						.maybe_with_attribute("class", if album.palette.home_page_album_needs_borders() {
@@ -389,17 +396,21 @@ pub fn make_home_page(all_albums: &[Album], all_remixes: &[Song], all_assists: &
 								})
 								.with_child({
 									let mut column = XmlNode::new("a-c")
-										.maybe_with_child(
-											if album.artist == "Astro" {
+										.maybe_with_child({
+											// eps, mixtapes and such say so above the title; plain albums and singles speak for themselves
+											let mut above_title: Vec<String> = Vec::new();
+											if album.artist != "Astro" {
+												above_title.push(smartquotes::smart_quotes(&album.artist));
+											}
+											if !matches!(album.release_type, ReleaseType::Album | ReleaseType::Single) {
+												above_title.push(album.release_type.label().to_string());
+											}
+											if above_title.is_empty() {
 												None
 											} else {
-												Some(XmlNode::new("h3")
-													.with_text(
-														smartquotes::smart_quotes(&album.artist)
-													)
-												)
+												Some(XmlNode::new("h3").with_text(above_title.join(" · ")))
 											}
-										)
+										})
 										.with_child(
 											XmlNode::new("h2")
 												.with_child(
@@ -409,11 +420,14 @@ pub fn make_home_page(all_albums: &[Album], all_remixes: &[Song], all_assists: &
 															smartquotes::smart_quotes(&album.title)
 														)
 												)
+												.maybe_with_child(
+													album.advisory().is_explicit().then(icons::explicit_badge)
+												)
 										);
 									if let Some(text) = &album.about {
 										for paragraph_slice in text {
 											let mut paragraph = smartquotes::smart_quotes(paragraph_slice);
-											for album_to_italicize in all_albums.iter().filter(|a| !a.single) {
+											for album_to_italicize in all_albums.iter().filter(|a| !a.is_single()) {
 												let title_text_to_replace = smartquotes::smart_quotes(&album_to_italicize.title);
 												/* paragraph = paragraph.replace(&title_text_to_replace, &format!(
 													"\0<a href=#{}\0>{}\0</a\0>",
@@ -430,7 +444,7 @@ pub fn make_home_page(all_albums: &[Album], all_remixes: &[Song], all_assists: &
 											)
 										}
 									}
-									if !album.single {
+									if !album.is_single() {
 										let useful_title_length = {
 											let max_title_length: usize = main_songs.iter().map(|s| s.title.len()).max().expect("No songs");
 											let average_title_length: usize = main_songs.iter().map(|s| s.title.len()).sum::<usize>() / main_songs.len();
@@ -527,10 +541,10 @@ pub fn make_home_page(all_albums: &[Album], all_remixes: &[Song], all_assists: &
 						)
 				));
 				// now should we also add entries for songs?
-				// if less than half of album was released as singles and non-compilation, and song.released_as_single, ya! mention me!
+				// if less than half of album was released as singles and it isn't a compilation, and song.released_as_single, ya! mention me!
 				// else if compilation, yes report them, but don't say "from [album name]"
 				let proportion_of_album_released_as_single = (main_songs.iter().filter(|s| s.released_as_single).collect::<Vec<_>>().len() as f32) / (main_songs.len() as f32);
-				if !album.compilation && proportion_of_album_released_as_single < 0.5 {
+				if album.release_type != ReleaseType::Compilation && proportion_of_album_released_as_single < 0.5 {
 					let mut the_singles: Vec<&Song> = album.songs.iter().filter(|s| s.released_as_single).collect();
 					the_singles.sort_by(|a, b| a.released.cmp(&b.released));
 					match the_singles.len() {
@@ -539,6 +553,7 @@ pub fn make_home_page(all_albums: &[Album], all_remixes: &[Song], all_assists: &
 								entries.push((
 									EntryType::Song,
 									&single.released,
+									ReleaseType::Single.to_string(),
 									song_xml(single, Some((album, "Single")), true)
 								));
 							}
@@ -548,17 +563,19 @@ pub fn make_home_page(all_albums: &[Album], all_remixes: &[Song], all_assists: &
 								entries.push((
 									EntryType::Song,
 									&single.released,
+									ReleaseType::Single.to_string(),
 									song_xml(single, Some((album, describe_single_number(single_number+1))), true)
 								));
 							}
 						}
 					}
-				} else if album.compilation {
+				} else if album.release_type == ReleaseType::Compilation {
 					for song in &album.songs {
 						if song.released < album.released {
 							entries.push((
 								EntryType::Song,
 								&song.released,
+								ReleaseType::Single.to_string(),
 								song_xml(song, None, !song.bonus)
 							));
 						}
@@ -569,6 +586,7 @@ pub fn make_home_page(all_albums: &[Album], all_remixes: &[Song], all_assists: &
 				entries.push((
 					EntryType::Song,
 					&remix.released,
+					remix.release_type.to_string(),
 					song_xml(remix, None, true)
 				))
 			}
@@ -576,6 +594,7 @@ pub fn make_home_page(all_albums: &[Album], all_remixes: &[Song], all_assists: &
 				entries.push((
 					EntryType::Assist,
 					&assist.released,
+					"assist",
 					XmlNode::new("a-h")
 						.with_child(
 							XmlNode::new("assist-c")
@@ -593,7 +612,10 @@ pub fn make_home_page(all_albums: &[Album], all_remixes: &[Song], all_assists: &
 								)
 								.with_child(
 									XmlNode::new("img")
-										.with_attribute("src", &assist.artwork)
+										.with_attribute(
+											"src",
+											format!("artwork/{}.jpg", assist.artwork.name_without_slash)
+										)
 										.with_attribute("loading", "lazy")
 										.with_attribute("aria-hidden", "true")
 										.with_attribute("alt", "")
@@ -602,13 +624,12 @@ pub fn make_home_page(all_albums: &[Album], all_remixes: &[Song], all_assists: &
 									XmlNode::new("div")
 										.with_child(
 											XmlNode::new("a")
-												.with_attribute("href", &assist.url)
-												.with_attribute("class", "external")
+												.with_attribute("href", format!("{}/", assist.slug))
 												.with_text(smartquotes::smart_quotes(&assist.titlable))
 										)
 										.with_child(
 											XmlNode::new("p")
-												.with_text(smartquotes::smart_quotes(&assist.role))
+												.with_text(assist.role.to_string())
 										)
 								)
 						)
@@ -616,9 +637,11 @@ pub fn make_home_page(all_albums: &[Album], all_remixes: &[Song], all_assists: &
 			}
 			entries.reverse(); // ensure any date-ties are actually newest-to-oldest
 			entries.sort_by(|a, b| b.1.cmp(a.1));
-			let mut main = XmlNode::new("main");
+			let mut main = XmlNode::new("main").with_child(type_filter(
+				&entries.iter().map(|(_, _, kind, _)| *kind).collect::<Vec<_>>()
+			));
 			let mut previous_entry_type = EntryType::Album;
-			for (entry_type, _, entry_xml) in entries {
+			for (entry_type, _, kind, entry_xml) in entries {
 				// entry types are EntryType::Album, EntryType::Assist, or EntryType::Song
 				if let Some(spacer_tag) = match (previous_entry_type, entry_type) {
 					(EntryType::Album, EntryType::Album) => None,
@@ -632,7 +655,7 @@ pub fn make_home_page(all_albums: &[Album], all_remixes: &[Song], all_assists: &
 						XmlNode::new(spacer_tag).with_text("")
 					);
 				}
-				main.add_child(entry_xml);
+				main.add_child(entry_xml.with_attribute("data-type", kind));
 				previous_entry_type = entry_type;
 			}
 			main
@@ -683,6 +706,45 @@ pub fn make_home_page(all_albums: &[Album], all_remixes: &[Song], all_assists: &
 	}
 }
 
+// a checkbox for every kind of entry on the page. unchecking one hides those entries (and the spacers before them)
+fn type_filter(kinds_present: &[&str]) -> XmlNode {
+	let mut kinds: Vec<(&str, String)> = ReleaseType::ALL
+		.iter()
+		.filter(|release_type| kinds_present.contains(&release_type.to_string()))
+		.map(|release_type| {
+			let label = release_type.label();
+			(
+				release_type.to_string(),
+				format!("{}{}", label, if label.ends_with('x') { "es" } else { "s" })
+			)
+		})
+		.collect();
+	if kinds_present.contains(&"assist") {
+		kinds.push(("assist", String::from("Assists")));
+	}
+	let mut form = XmlNode::new("type-filter").with_child(XmlNode::new("span").with_text("Show"));
+	let mut style = String::new();
+	for (kind, label) in kinds {
+		let id = format!("show-{}", kind);
+		form.add_child(
+			XmlNode::new("input")
+				.with_attribute("type", "checkbox")
+				.with_attribute("id", &id)
+				.with_attribute("checked", "")
+		);
+		form.add_child(
+			XmlNode::new("label")
+				.with_attribute("for", &id)
+				.with_text(label)
+		);
+		style.push_str(&format!(
+			"body:has(#{}:not(:checked)) :is([data-type={}],:is(sp-1,sp-4):has(+[data-type={}])){{display:none}}",
+			id, kind, kind
+		));
+	}
+	form.with_child(XmlNode::new("style").with_text_unescaped(style))
+}
+
 fn describe_single_number(single_number: usize) -> &'static str {
 	match single_number {
 		0 => unreachable!(),
@@ -1,12 +1,13 @@
 // Responsible for creating link pages for Titlables at music.astronomy487.com/slug
 
-use crate::build::{icons, pages::lyricpage, smartquotes, xml::XmlNode};
+use crate::build::{icons, minify, pages::lyricpage, smartquotes, xml::XmlNode};
 use crate::fileops;
 use crate::globals;
 use crate::media::{
-	album::Album, audiocodec::AudioCodec, lyric::Lyrics, song::Song, titlable::Titlable
+	album::Album, audiocodec::AudioCodec, credit, credit::Role, lyric::Lyrics, song::Song,
+	titlable::Titlable
 };
-use crate::types::urlset::UrlSet;
+use crate::types::{color::Palette, releasetype::ReleaseType, urlset::UrlSet};
 
 pub fn make_link_page(
 	titlable: &Titlable, all_albums: &[Album], everything_should_be_encoded: bool
@@ -59,20 +60,20 @@ pub fn make_link_page(
 	} else {
 		match titlable {
 			Titlable::Song(song) => song.lyrics.as_ref(),
-			Titlable::Album(album) if album.single => album.songs[0].lyrics.as_ref(),
+			Titlable::Album(album) if album.is_single() => album.songs[0].lyrics.as_ref(),
 			Titlable::Album(_) => None
 		}
 	};
 
 	if let Some(lxs) = lyrics_to_provide {
 		let song: &Song = match titlable {
-			Titlable::Album(album) if album.single => &album.songs[0],
+			Titlable::Album(album) if album.is_single() => &album.songs[0],
 			Titlable::Album(_) => panic!(
 				"Trying to provide a link page to a non-single album; this shouldn't happen at runtime"
 			),
 			Titlable::Song(song) => song
 		};
-		lyricpage::make_lyric_page(song, lxs);
+		lyricpage::make_lyric_page(song, lxs, all_albums);
 	}
 
 	// 3. Make the url redirects
@@ -99,102 +100,44 @@ pub fn make_link_page(
 	} */
 
 	// 4. Make the big file
-	let mut head = XmlNode::new("head")
-		.with_child(XmlNode::new("meta").with_attribute("charset", "utf-8"))
-		.with_child(
-			XmlNode::new("link")
-				.with_attribute("rel", "icon")
-				.with_attribute("href", "../favicon.ico")
-				.with_attribute("type", "image/ico")
-		)
-		.with_child(
-			XmlNode::new("link")
-				.with_attribute("rel", "canonical")
-				.with_attribute("href", &canonical_url)
-		)
-		.with_child(
-			XmlNode::new("link")
-				.with_attribute("rel", "stylesheet")
-				.with_attribute("href", "../linkpage-style.css")
-		)
-		.with_child(XmlNode::new("title").with_text(&format_title))
-		.with_child(XmlNode::new("style").with_text(palette.style_tag()))
-		.with_child(
-			XmlNode::new("meta")
-				.with_attribute("name", "theme-color")
-				.with_attribute("content", palette.html_theme_color())
-		)
-		.with_child(
-			XmlNode::new("meta")
-				.with_attribute("name", "description")
-				.with_attribute("content", &description)
-		)
-		.with_child(
-			XmlNode::new("meta")
-				.with_attribute("name", "keywords")
-				.with_attribute("content", globals::OG_KEYWORDS)
-		)
-		.with_child(
-			XmlNode::new("meta")
-				.with_attribute("name", "author")
-				.with_attribute("content", globals::OG_AUTHOR)
-		)
-		.with_child(
-			XmlNode::new("meta")
-				.with_attribute("name", "robots")
-				.with_attribute("content", globals::OG_ROBOTS)
-		)
-		.with_child(
-			XmlNode::new("meta")
-				.with_attribute("property", "og:site_name")
-				.with_attribute("content", globals::OG_SITE_NAME)
-		)
-		.with_child(
-			XmlNode::new("meta")
-				.with_attribute("property", "og:title")
-				.with_attribute("content", &format_title)
-		)
-		.with_child(
-			XmlNode::new("meta")
-				.with_attribute("property", "og:description")
-				.with_attribute("content", description)
-		)
-		.with_child(
-			XmlNode::new("meta")
-				.with_attribute("property", "og:image")
-				.with_attribute("content", open_graph_artwork)
-		)
-		.with_child(
-			XmlNode::new("link")
-				.with_attribute("rel", "apple-touch-icon")
-				.with_attribute("href", open_graph_artwork)
-		)
-		.with_child(
-			XmlNode::new("meta")
-				.with_attribute("property", "og:url")
-				.with_attribute("content", canonical_url)
-		)
-		.with_child(
-			XmlNode::new("meta")
-				.with_attribute("property", "music:musician")
-				.with_attribute("content", "https://www.astronomy487.com/")
-		)
-		.with_child(
-			XmlNode::new("meta")
-				.with_attribute("property", "music:release_date")
-				.with_attribute("content", released.to_iso8601())
-		)
-		.with_child(
-			XmlNode::new("meta")
-				.with_attribute("property", "music:duration")
-				.with_attribute("content", duration.seconds().to_string())
-		);
+	let mut head = page_head(
+		&canonical_url,
+		&format_title,
+		palette,
+		&description,
+		open_graph_artwork
+	)
+	.with_child(
+		XmlNode::new("meta")
+			.with_attribute("name", "keywords")
+			.with_attribute("content", globals::OG_KEYWORDS)
+	)
+	.with_child(
+		XmlNode::new("link")
+			.with_attribute("rel", "apple-touch-icon")
+			.with_attribute("href", open_graph_artwork)
+	)
+	.with_child(
+		XmlNode::new("meta")
+			.with_attribute("property", "music:musician")
+			.with_attribute("content", "https://www.astronomy487.com/")
+	)
+	.with_child(
+		XmlNode::new("meta")
+			.with_attribute("property", "music:release_date")
+			.with_attribute("content", released.to_iso8601())
+	)
+	.with_child(
+		XmlNode::new("meta")
+			.with_attribute("property", "music:duration")
+			.with_attribute("content", duration.seconds().to_string())
+	);
 	match &titlable {
 		Titlable::Album(album) => {
 			head.add_child(
 				XmlNode::new("meta")
 					.with_attribute("property", "og:type")
-					.with_attribute("content", "music.album")
+					.with_attribute("content", album.release_type.og_type())
 			);
 			for (i, song) in album.songs.iter().enumerate() {
 				if !song.bonus {
@@ -258,7 +201,16 @@ pub fn make_link_page(
 				)
 				.with_attribute("alt", smartquotes::smart_quotes(&artwork.caption))
 		}))
-		.with_child(XmlNode::new("h1").with_text(smartquotes::smart_quotes(&format_title_short)))
+		.with_child(
+			XmlNode::new("h1")
+				.with_text(smartquotes::smart_quotes(&format_title_short))
+				.maybe_with_child(
+					titlable
+						.advisory()
+						.is_explicit()
+						.then(icons::explicit_badge)
+				)
+		)
 		.with_child(
 			XmlNode::new("table")
 				.with_attribute("class", "metadata")
@@ -270,7 +222,7 @@ pub fn make_link_page(
 							released.to_display()
 						)))
 						.maybe_with_child(match titlable {
-							Titlable::Album(album) if !album.single => {
+							Titlable::Album(album) if !album.is_single() => {
 								Some(XmlNode::new("td").with_text({
 									let amount = album.songs.iter().filter(|s| !s.bonus).count();
 									format!(
@@ -306,7 +258,46 @@ pub fn make_link_page(
 						} else {
 							Some(XmlNode::new("td").with_text(duration.display()))
 						})
+						.maybe_with_child(titlable.unreleased().then(|| {
+							// filled in by linkpage-countdown.js
+							XmlNode::new("td").with_child(
+								XmlNode::new("count-down")
+									.with_attribute(
+										"data-release",
+										titlable.release_time().to_iso8601(released)
+									)
+									.with_text("")
+							)
+						}))
 				)
+				.maybe_with_child(lyrics_to_provide.map(|lyrics| {
+					let mut td = XmlNode::new("td")
+						.with_attribute("colspan", "3")
+						.with_text("Vocals by ");
+					let credits = lyrics.credits();
+					for (index, vocalist) in credits.iter().enumerate() {
+						if index > 0 {
+							td.add_text(globals::ARTIST_SEPARATOR);
+						}
+						match &vocalist.url {
+							Some(url) => td.add_child(
+								XmlNode::new("a")
+									.with_attribute("href", url)
+									.with_text(&vocalist.name)
+							),
+							None => td.add_text(&vocalist.name)
+						}
+					}
+					XmlNode::new("tr").with_child(td)
+				}))
+		)
+		.maybe_with_child(
+			(titlable.unreleased() && !url_set.entries().is_empty()).then(|| {
+				// streaming links before release day are pre-saves
+				XmlNode::new("p")
+					.with_attribute("class", "presave")
+					.with_text("Pre-save")
+			})
 		)
 		.with_child({
 			let mut table = XmlNode::new("table").with_attribute("class", "streamlinks");
@@ -339,13 +330,31 @@ pub fn make_link_page(
 			}
 			table
 		});
+	// timestamped dj mixes get a player that their tracklist can seek
+	let seekable = match titlable {
+		Titlable::Song(song) => !song.unreleased && !song.chapters().is_empty(),
+		Titlable::Album(_) => false
+	};
+	if seekable {
+		body.add_child(
+			XmlNode::new("audio")
+				.with_attribute("controls", "")
+				.with_attribute("preload", "none")
+				.with_attribute("src", titlable.audio_download_url(&AudioCodec::Mp3))
+				.with_text("")
+		);
+	}
+	for table in related_tables(titlable, seekable) {
+		body.add_child(table);
+	}
 
-	let maybe_details = if let Some(about) = titlable.about() {
+	let maybe_credits_table = credits_table(titlable, all_albums);
+	let maybe_details = if titlable.about().is_some() || maybe_credits_table.is_some() {
 		let mut details = XmlNode::new("details").with_child(XmlNode::new("summary").with_text("See more"));
-		for paragraph in about {
+		for paragraph in titlable.about().into_iter().flatten() {
 			details.add_child(XmlNode::new("p").with_text(smartquotes::smart_quotes(paragraph)));
 		}
-		Some(details)
+		Some(details.maybe_with_child(maybe_credits_table))
 	} else {
 		None
 	};
@@ -384,6 +393,18 @@ pub fn make_link_page(
 					))
 				));
 			}
+			// dj mix tracklists come with a cue sheet for each codec
+			if let Titlable::Song(song) = titlable
+				&& !song.chapters().is_empty()
+			{
+				let cue_name = format!("tracklist-{}.cue", codec.ext());
+				fileops::write_file(&destination_folder.join(&cue_name), song.cue_sheet(&codec));
+				links_to_provide.push((
+					format!("{} cue", codec.ext()),
+					cue_name,
+					Some(format!("{}.cue", titlable.format_title()))
+				));
+			}
 		}
 		if lyrics_to_provide.is_some() {
 			links_to_provide.push((String::from("Lyrics"), String::from("lyrics/"), None));
@@ -428,7 +449,7 @@ pub fn make_link_page(
 
 	/* let mut metadata_to_show: Vec<(&'static str, String)> = Vec::new();
 	metadata_to_show.push(("Type", match titlable {
-		Titlable::Album(album) if album.single => "Single",
+		Titlable::Album(album) if album.is_single() => "Single",
 		Titlable::Album(_) => "Album",
 		Titlable::Song(song) if song.parent_album_indices.is_some() => "Song",
 		Titlable::Song(_) => "Remix"
@@ -442,7 +463,7 @@ pub fn make_link_page(
 	if let Titlable::Song(song) = titlable && let Some(isrc) = &song.isrc {
 		metadata_to_show.push(("ISRC", format!("{}", isrc)));
 	}
-	if let Titlable::Album(album) = titlable && album.single && let Some(isrc) = &album.songs[0].isrc {
+	if let Titlable::Album(album) = titlable && album.is_single() && let Some(isrc) = &album.songs[0].isrc {
 		metadata_to_show.push(("ISRC", format!("{}", isrc)));
 	}
 	if let Titlable::Album(album) = titlable && let Some(upc) = &album.upc {
@@ -464,6 +485,21 @@ pub fn make_link_page(
 		details.add_child(table);
 	} */
 
+	if titlable.unreleased() {
+		body.add_child(
+			XmlNode::new("script").with_text_unescaped(minify::compress_js(include_str!(
+				"../../assets/linkpage-countdown.js"
+			)))
+		);
+	}
+	if seekable {
+		body.add_child(
+			XmlNode::new("script").with_text_unescaped(minify::compress_js(include_str!(
+				"../../assets/linkpage-tracklist.js"
+			)))
+		);
+	}
+
 	let html = XmlNode::new("html")
 		.with_attribute("lang", "en")
 		.with_child(head)
@@ -476,3 +512,192 @@ pub fn make_link_page(
 			.unwrap_or_else(|_| panic!("Couldn't write to file {}", index_html_location.display()));
 	}
 }
+
+// the <head> every link page starts with (assist pages too). callers add their own og:type and such
+pub fn page_head(
+	canonical_url: &str, format_title: &str, palette: &Palette, description: &str,
+	artwork_url: &str
+) -> XmlNode {
+	XmlNode::new("head")
+		.with_child(XmlNode::new("meta").with_attribute("charset", "utf-8"))
+		.with_child(
+			XmlNode::new("link")
+				.with_attribute("rel", "icon")
+				.with_attribute("href", "../favicon.ico")
+				.with_attribute("type", "image/ico")
+		)
+		.with_child(
+			XmlNode::new("link")
+				.with_attribute("rel", "canonical")
+				.with_attribute("href", canonical_url)
+		)
+		.with_child(
+			XmlNode::new("link")
+				.with_attribute("rel", "stylesheet")
+				.with_attribute("href", "../linkpage-style.css")
+		)
+		.with_child(XmlNode::new("title").with_text(format_title))
+		.with_child(XmlNode::new("style").with_text(palette.style_tag()))
+		.with_child(
+			XmlNode::new("meta")
+				.with_attribute("name", "theme-color")
+				.with_attribute("content", palette.html_theme_color())
+		)
+		.with_child(
+			XmlNode::new("meta")
+				.with_attribute("name", "description")
+				.with_attribute("content", description)
+		)
+		.with_child(
+			XmlNode::new("meta")
+				.with_attribute("name", "author")
+				.with_attribute("content", globals::OG_AUTHOR)
+		)
+		.with_child(
+			XmlNode::new("meta")
+				.with_attribute("name", "robots")
+				.with_attribute("content", globals::OG_ROBOTS)
+		)
+		.with_child(
+			XmlNode::new("meta")
+				.with_attribute("property", "og:site_name")
+				.with_attribute("content", globals::OG_SITE_NAME)
+		)
+		.with_child(
+			XmlNode::new("meta")
+				.with_attribute("property", "og:title")
+				.with_attribute("content", format_title)
+		)
+		.with_child(
+			XmlNode::new("meta")
+				.with_attribute("property", "og:description")
+				.with_attribute("content", description)
+		)
+		.with_child(
+			XmlNode::new("meta")
+				.with_attribute("property", "og:image")
+				.with_attribute("content", artwork_url)
+		)
+		.with_child(
+			XmlNode::new("meta")
+				.with_attribute("property", "og:url")
+				.with_attribute("content", canonical_url)
+		)
+}
+
+// captioned lists of other pages under the streaming links; each row is (th, td)
+fn related_table(caption: &str, rows: Vec<(XmlNode, XmlNode)>) -> Option<XmlNode> {
+	if rows.is_empty() {
+		return None;
+	}
+	let mut table = XmlNode::new("table")
+		.with_attribute("class", "related")
+		.with_child(XmlNode::new("caption").with_text(caption));
+	for (th, td) in rows {
+		table.add_child(XmlNode::new("tr").with_child(th).with_child(td));
+	}
+	Some(table)
+}
+
+fn page_link(slug: &str, title: &str) -> XmlNode {
+	XmlNode::new("td").with_child(
+		XmlNode::new("a")
+			.with_attribute("href", format!("../{}/", slug))
+			.with_text(smartquotes::smart_quotes(title))
+	)
+}
+
+// with seekable, mix tracklist timestamps jump the page's audio there (see linkpage-tracklist.js)
+fn related_tables(titlable: &Titlable, seekable: bool) -> Vec<XmlNode> {
+	let song = match titlable {
+		Titlable::Album(album) if album.is_single() => &album.songs[0],
+		Titlable::Album(_) => return Vec::new(),
+		Titlable::Song(song) => song
+	};
+	let versions = related_table(
+		"Other versions",
+		song.other_versions
+			.iter()
+			.map(|(slug, title, kind)| {
+				(
+					XmlNode::new("th")
+						.with_text(kind.map_or("Original", |version_kind| version_kind.label())),
+					page_link(slug, title)
+				)
+			})
+			.collect()
+	);
+	let samples = related_table(
+		if song.release_type == ReleaseType::DjMix {
+			"Mix tracklist"
+		} else {
+			"Samples"
+		},
+		song.samples
+			.iter()
+			.map(|sample| {
+				(
+					match sample.at {
+						Some(at) if seekable => XmlNode::new("th").with_child(
+							XmlNode::new("a")
+								.with_attribute("href", "#")
+								.with_attribute("data-seek", at.seconds().to_string())
+								.with_text(at.timestamp())
+						),
+						Some(at) => XmlNode::new("th").with_text(at.timestamp()),
+						None => XmlNode::new("th").with_text("")
+					},
+					match &sample.slug {
+						Some(slug) => page_link(slug, &sample.format_title()),
+						None => XmlNode::new("td")
+							.with_text(smartquotes::smart_quotes(&sample.format_title()))
+					}
+				)
+			})
+			.collect()
+	);
+	let sampled_by = related_table(
+		"Sampled by",
+		song.sampled_by
+			.iter()
+			.map(|(slug, title)| (XmlNode::new("th").with_text(""), page_link(slug, title)))
+			.collect()
+	);
+	[versions, samples, sampled_by]
+		.into_iter()
+		.flatten()
+		.collect()
+}
+
+fn credits_table(titlable: &Titlable, all_albums: &[Album]) -> Option<XmlNode> {
+	let credits = match titlable {
+		Titlable::Album(album) if album.is_single() => album.songs[0].all_credits(all_albums),
+		Titlable::Album(album) => album.credits.clone(),
+		Titlable::Song(song) => song.all_credits(all_albums)
+	};
+	let mut rows: Vec<(&'static str, String)> = Role::ALL
+		.iter()
+		.filter_map(|role| {
+			let names = credit::names_for(&credits, *role);
+			(!names.is_empty()).then(|| (role.label(), credit::join_names(&names)))
+		})
+		.collect();
+	if let Some(artwork_credit) = titlable
+		.artwork()
+		.and_then(|artwork| artwork.credit.as_ref())
+	{
+		rows.push(("Cover art by", artwork_credit.clone()));
+	}
+	if rows.is_empty() {
+		return None;
+	}
+	let mut table = XmlNode::new("table").with_attribute("class", "credits");
+	for (label, names) in rows {
+		table.add_child(
+			XmlNode::new("tr")
+				.with_child(XmlNode::new("th").with_text(label))
+				.with_child(XmlNode::new("td").with_text(smartquotes::smart_quotes(&names)))
+		);
+	}
+	Some(table)
+}
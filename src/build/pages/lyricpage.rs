@@ -1,12 +1,13 @@
 use crate::build::{icons, smartquotes, xml::XmlNode};
 use crate::globals;
+use crate::media::album::Album;
 use crate::media::song::Song;
 
 use crate::media::audiocodec::AudioCodec;
 use crate::media::lyric;
-use lyric::Lyrics;
+use lyric::{LyricLayer, Lyrics};
 
-pub fn make_lyric_page(song: &Song, lyrics: &Lyrics) {
+pub fn make_lyric_page(song: &Song, lyrics: &Lyrics, all_albums: &[Album]) {
 	let destination_folder = globals::filezone()
 		.join("music.astronomy487.com")
 		.join(&song.slug)
@@ -18,14 +19,33 @@ pub fn make_lyric_page(song: &Song, lyrics: &Lyrics) {
 		)
 	});
 
+	let lyric_context = song.lyric_context(all_albums);
 	for codec in lyric::ALL_TEXT_CODECS {
 		let lyrics_location = destination_folder
 			.join("lyrics")
 			.with_extension(codec.ext());
 		let mut file = std::fs::File::create(&lyrics_location)
 			.unwrap_or_else(|_| panic!("Couldn't create file {}", lyrics_location.display()));
-		let _ = std::io::Write::write(&mut file, lyrics.as_filetype(codec).as_bytes())
+		let _ = std::io::Write::write(
+			&mut file,
+			lyrics.as_filetype(codec, &lyric_context).as_bytes()
+		)
+		.unwrap_or_else(|_| panic!("Couldn't write to file {}", lyrics_location.display()));
+	}
+	let layers = lyrics.layers();
+	for layer in &layers {
+		let layered_lyrics = lyrics.with_layer(*layer);
+		for codec in lyric::LAYER_TEXT_CODECS {
+			let lyrics_location =
+				destination_folder.join(format!("lyrics.{}.{}", layer.file_tag(), codec.ext()));
+			let mut file = std::fs::File::create(&lyrics_location)
+				.unwrap_or_else(|_| panic!("Couldn't create file {}", lyrics_location.display()));
+			let _ = std::io::Write::write(
+				&mut file,
+				layered_lyrics.as_filetype(codec, &lyric_context).as_bytes()
+			)
 			.unwrap_or_else(|_| panic!("Couldn't write to file {}", lyrics_location.display()));
+		}
 	}
 
 	let webpage_title = format!("{} (Lyrics)", song.format_title());
@@ -37,67 +57,106 @@ pub fn make_lyric_page(song: &Song, lyrics: &Lyrics) {
 		None => "../../squarelogo.png"
 	};
 
-	let body = XmlNode::new("body")
+	let mut header = XmlNode::new("header")
+		/* .with_child(
+			XmlNode::new("h3")
+				.with_child(
+					XmlNode::new("a")
+						.with_attribute("href", "../")
+						.with_text("Go back")
+				)
+		) */
+		.with_child(XmlNode::new("h3").with_text("Lyrics"))
 		.with_child(
-			XmlNode::new("header")
-				/* .with_child(
-					XmlNode::new("h3")
-						.with_child(
-							XmlNode::new("a")
-								.with_attribute("href", "../")
-								.with_text("Go back")
+			XmlNode::new("h2").with_text(smartquotes::smart_quotes(&song.format_title_short()))
+		)
+		.with_child({
+			let mut the_downloads = XmlNode::new("the-downloads");
+			for codec in lyric::ALL_TEXT_CODECS {
+				the_downloads.add_child(
+					XmlNode::new("a")
+						.with_attribute("href", format!("lyrics.{}", codec.ext()))
+						.with_attribute(
+							"download",
+							format!("{} (Lyrics).{}", song.public_filename(), codec.ext())
+						)
+						.with_child(icons::inline_download_icon_svg())
+						.with_child(XmlNode::new("span").with_text(
+							// codec.ext().to_uppercase()
+							//format!(".{}", codec.ext())
+							codec.description()
+						))
+				)
+			}
+			the_downloads
+		});
+	for layer in &layers {
+		let mut the_downloads = XmlNode::new("the-downloads")
+			.with_child(XmlNode::new("span").with_text(layer.description()));
+		for codec in lyric::LAYER_TEXT_CODECS {
+			the_downloads.add_child(
+				XmlNode::new("a")
+					.with_attribute(
+						"href",
+						format!("lyrics.{}.{}", layer.file_tag(), codec.ext())
+					)
+					.with_attribute(
+						"download",
+						format!(
+							"{} (Lyrics, {}).{}",
+							song.public_filename(),
+							layer.description(),
+							codec.ext()
 						)
-				) */
-				.with_child(XmlNode::new("h3").with_text("Lyrics"))
+					)
+					.with_child(icons::inline_download_icon_svg())
+					.with_child(XmlNode::new("span").with_text(codec.description()))
+			)
+		}
+		header.add_child(the_downloads);
+	}
+	header.add_child({
+		let all_vocalist_sets = lyrics.all_vocalist_sets();
+		let mut form = if all_vocalist_sets.len() > 1 {
+			// let vocalists = lyrics.all_vocalists();
+			XmlNode::new("form")
 				.with_child(
-					XmlNode::new("h2")
-						.with_text(smartquotes::smart_quotes(&song.format_title_short()))
+					XmlNode::new("input")
+						.with_attribute("type", "checkbox")
+						.with_attribute("id", "show-vocalists")
+						.with_attribute("name", "show-vocalists")
 				)
-				.with_child({
-					let mut the_downloads = XmlNode::new("the-downloads");
-					for codec in lyric::ALL_TEXT_CODECS {
-						the_downloads.add_child(
-							XmlNode::new("a")
-								.with_attribute("href", format!("lyrics.{}", codec.ext()))
-								.with_attribute(
-									"download",
-									format!("{} (Lyrics).{}", song.public_filename(), codec.ext())
-								)
-								.with_child(icons::inline_download_icon_svg())
-								.with_child(XmlNode::new("span").with_text(
-									// codec.ext().to_uppercase()
-									//format!(".{}", codec.ext())
-									codec.description()
-								))
+				.with_child(
+					XmlNode::new("label")
+						.with_attribute("for", "show-vocalists")
+						.with_text(
+							// format!("Show {} vocalists", vocalists.len())
+							"Show vocalists"
 						)
-					}
-					the_downloads
-				})
-				.with_child({
-					let all_vocalist_sets = lyrics.all_vocalist_sets();
-					if all_vocalist_sets.len() > 1 {
-						// let vocalists = lyrics.all_vocalists();
-						XmlNode::new("form")
-							.with_child(
-								XmlNode::new("input")
-									.with_attribute("type", "checkbox")
-									.with_attribute("id", "show-vocalists")
-									.with_attribute("name", "show-vocalists")
-							)
-							.with_child(
-								XmlNode::new("label")
-									.with_attribute("for", "show-vocalists")
-									.with_text(
-										// format!("Show {} vocalists", vocalists.len())
-										"Show vocalists"
-									)
-							)
-					} else {
-						XmlNode::new("form")
-							.with_text(format!("Vocals by {}", all_vocalist_sets[0]))
-					}
-				})
-		)
+				)
+		} else {
+			XmlNode::new("form").with_child(
+				XmlNode::new("span").with_text(format!("Vocals by {}", all_vocalist_sets[0]))
+			)
+		};
+		for layer in &layers {
+			let id = format!("show-{}", layer.file_tag());
+			form.add_child(
+				XmlNode::new("input")
+					.with_attribute("type", "checkbox")
+					.with_attribute("id", &id)
+					.with_attribute("name", &id)
+			);
+			form.add_child(
+				XmlNode::new("label")
+					.with_attribute("for", &id)
+					.with_text(format!("Show {}", layer.description().to_lowercase()))
+			);
+		}
+		form
+	});
+	let body = XmlNode::new("body")
+		.with_child(header)
 		.with_child(lyrics.lyric_page_xml())
 		.with_child(XmlNode::new("script").with_text_unescaped(format!(
 			"const source = \"{}\";",
@@ -111,7 +170,11 @@ pub fn make_lyric_page(song: &Song, lyrics: &Lyrics) {
 	let head = XmlNode::new("head")
 		.with_child(XmlNode::new("meta").with_attribute("charset", "UTF-8"))
 		.with_child(XmlNode::new("title").with_text(&webpage_title))
-		.with_child(XmlNode::new("style").with_text(song.palette.style_tag()))
+		.with_child(XmlNode::new("style").with_text(format!(
+			"{}{}",
+			song.palette.style_tag(),
+			layers.iter().map(layer_style).collect::<String>()
+		)))
 		.with_child(
 			XmlNode::new("link")
 				.with_attribute("rel", "icon")
@@ -196,3 +259,16 @@ pub fn make_lyric_page(song: &Song, lyrics: &Lyrics) {
 			.unwrap_or_else(|_| panic!("Couldn't write to file {}", index_html_location.display()));
 	}
 }
+
+// each layer's lines only show up while its checkbox is ticked
+fn layer_style(layer: &LyricLayer) -> String {
+	let selector = match layer {
+		LyricLayer::Translation(language) => format!("l-t[lang={}]", language.iso_639_1()),
+		LyricLayer::Romanization => String::from("l-r")
+	};
+	format!(
+		"body:has(#show-{}:checked) {}{{display:block}}",
+		layer.file_tag(),
+		selector
+	)
+}
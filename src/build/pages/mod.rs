@@ -1,5 +1,7 @@
+pub mod assistpage;
 pub mod homepage;
 pub mod linkpage;
 pub mod lyricpage;
+pub mod redirects;
 pub mod rss;
 pub mod sitemap;
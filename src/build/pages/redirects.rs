@@ -0,0 +1,147 @@
+// Responsible for pointing previous slugs at where things live now
+// pages get a cloudflare pages _redirects file; audio lives in r2, which can't redirect on its own,
+// so those go in a csv for a cloudflare bulk redirect list
+
+use crate::fileops;
+use crate::globals;
+use crate::media::{album::Album, audiocodec::AudioCodec, song::Song, titlable::Titlable};
+
+pub fn make_redirects(all_albums: &[Album], all_remixes: &[Song]) {
+	let mut page_redirects = String::new();
+	let mut audio_redirects = String::new();
+
+	// old_slug is where `to` used to be. each audio pair is (what was served under old_slug, what serves it now)
+	let mut redirect = |old_slug: &str, to: &Titlable, audio: &[(&Titlable, &Titlable)]| {
+		let audio_urls: Vec<(String, String)> = audio
+			.iter()
+			.flat_map(|(was, now)| {
+				[AudioCodec::Mp3, AudioCodec::Flac].map(|codec| {
+					(
+						was.audio_download_url_for_slug(&codec, old_slug),
+						now.audio_download_url(&codec)
+					)
+				})
+			})
+			.collect();
+		let (pages, audio_lines) = redirect_lines(old_slug, to.slug(), &audio_urls);
+		page_redirects.push_str(&pages);
+		audio_redirects.push_str(&audio_lines);
+	};
+
+	for album in all_albums {
+		let album_titlable = Titlable::Album(album);
+		if let Some((album_index, song_index)) = album.folded_into {
+			// a folded temporary single goes to the album track it became, audio and all
+			let track = Titlable::Song(&all_albums[album_index].songs[song_index]);
+			let single_song = Titlable::Song(&album.songs[0]);
+			for old_slug in std::iter::once(&album.slug).chain(album.previous_slugs.iter()) {
+				redirect(
+					old_slug,
+					&track,
+					&[(&album_titlable, &track), (&single_song, &track)]
+				);
+			}
+		} else if album.is_single() {
+			// a single's song shares the single's slug, so its audio moves along with it
+			let single_song = Titlable::Song(&album.songs[0]);
+			for previous_slug in &album.previous_slugs {
+				redirect(
+					previous_slug,
+					&album_titlable,
+					&[
+						(&album_titlable, &album_titlable),
+						(&single_song, &single_song)
+					]
+				);
+			}
+		} else {
+			for previous_slug in &album.previous_slugs {
+				redirect(
+					previous_slug,
+					&album_titlable,
+					&[(&album_titlable, &album_titlable)]
+				);
+			}
+			for song in &album.songs {
+				let song_titlable = Titlable::Song(song);
+				for previous_slug in &song.previous_slugs {
+					redirect(
+						previous_slug,
+						&song_titlable,
+						&[(&song_titlable, &song_titlable)]
+					);
+				}
+			}
+		}
+	}
+	for remix in all_remixes {
+		let remix_titlable = Titlable::Song(remix);
+		for previous_slug in &remix.previous_slugs {
+			redirect(
+				previous_slug,
+				&remix_titlable,
+				&[(&remix_titlable, &remix_titlable)]
+			);
+		}
+	}
+
+	fileops::write_file(
+		&globals::filezone()
+			.join("music.astronomy487.com")
+			.join("_redirects"),
+		page_redirects
+	);
+	fileops::write_file(
+		&globals::filezone()
+			.join("private")
+			.join("audio-redirects")
+			.with_extension("csv"),
+		audio_redirects
+	);
+}
+
+// the _redirects and csv lines for one old slug. a temporary single can share its slug with the
+// track it becomes, so anything that would point at itself is left out
+fn redirect_lines(
+	old_slug: &str, new_slug: &str, audio_urls: &[(String, String)]
+) -> (String, String) {
+	let mut pages = String::new();
+	if old_slug != new_slug {
+		// the splat covers the lyric page and its files too
+		pages.push_str(&format!("/{} /{}/ 301\n", old_slug, new_slug));
+		pages.push_str(&format!("/{}/* /{}/:splat 301\n", old_slug, new_slug));
+	}
+	let mut audio = String::new();
+	for (was, now) in audio_urls {
+		if was != now {
+			audio.push_str(&format!(
+				"{},{},301\n",
+				was.trim_start_matches("https://"),
+				now
+			));
+		}
+	}
+	(pages, audio)
+}
+
+#[test]
+fn single_sharing_its_tracks_slug_skips_self_redirects() {
+	let zip = "https://audio.astronomy487.com/mp3/astro-song.zip".to_string();
+	let song = "https://audio.astronomy487.com/mp3/astro-song.mp3".to_string();
+	// the single's zip still has somewhere to go, but its song file is already the track's
+	let (pages, audio) = redirect_lines(
+		"song",
+		"song",
+		&[(zip.clone(), song.clone()), (song.clone(), song.clone())]
+	);
+	assert_eq!(pages, "");
+	assert_eq!(
+		audio,
+		"audio.astronomy487.com/mp3/astro-song.zip,https://audio.astronomy487.com/mp3/astro-song.mp3,301\n"
+	);
+	let (renamed_pages, _) = redirect_lines("old-song", "song", &[]);
+	assert_eq!(
+		renamed_pages,
+		"/old-song /song/ 301\n/old-song/* /song/:splat 301\n"
+	);
+}
@@ -3,7 +3,7 @@ use crate::fileops;
 use crate::globals;
 use crate::media::artwork::Artwork;
 use crate::media::{album::Album, assist::Assist, song::Song, titlable::Titlable};
-use crate::types::date::Date;
+use crate::types::date::{Date, ReleaseTime};
 
 pub fn make_rss(all_albums: &[Album], all_remixes: &[Song], all_assists: &[Assist]) {
 	let mut channel = XmlNode::new("channel")
@@ -41,19 +41,24 @@ pub fn make_rss(all_albums: &[Album], all_remixes: &[Song], all_assists: &[Assis
 				.with_attribute("type", "application/rss+xml")
 		);
 	let mut rss_entries_to_make: Vec<(XmlNode, &Date)> = Vec::new();
-	for album in all_albums {
+	// unreleased things (including scheduled ones) stay out until they're out
+	// folded temporary singles show up as their album track instead
+	for album in all_albums
+		.iter()
+		.filter(|album| !album.unreleased && album.folded_into.is_none())
+	{
 		rss_entries_to_make.push((
 			rss_item_for_titlable(Titlable::Album(album)),
 			&album.released
 		));
 		for song in &album.songs {
-			if song.released_as_single && song.released <= album.released {
+			if song.released_as_single && song.released <= album.released && !song.unreleased {
 				rss_entries_to_make
 					.push((rss_item_for_titlable(Titlable::Song(song)), &song.released));
 			}
 		}
 	}
-	for remix in all_remixes {
+	for remix in all_remixes.iter().filter(|remix| !remix.unreleased) {
 		rss_entries_to_make.push((
 			rss_item_for_titlable(Titlable::Song(remix)),
 			&remix.released
@@ -77,6 +82,7 @@ pub fn make_rss(all_albums: &[Album], all_remixes: &[Song], all_assists: &[Assis
 		.with_attribute("version", "2.0")
 		.with_attribute("xmlns:atom", "http://www.w3.org/2005/Atom")
 		.with_attribute("xmlns:media", "http://search.yahoo.com/mrss/")
+		.with_attribute("xmlns:itunes", "http://www.itunes.com/dtds/podcast-1.0.dtd")
 		.with_child(channel);
 
 	let mut file = std::fs::File::create(
@@ -116,10 +122,7 @@ fn rss_item_for_titlable(titlable: Titlable) -> XmlNode {
 		.with_child(
 			XmlNode::new("category")
 				.with_attribute("domain", "format")
-				.with_text(match titlable {
-					Titlable::Album(_) => "album",
-					Titlable::Song(_) => "song"
-				})
+				.with_text(titlable.release_type().to_string())
 		)
 		.with_child(
 			XmlNode::new("category")
@@ -131,39 +134,26 @@ fn rss_item_for_titlable(titlable: Titlable) -> XmlNode {
 				.with_attribute("url", "https://music.astronomy487.com/rss.xml")
 				.with_text("Astro's discography")
 		)
+		.with_child(artwork_enclosure(image_name_without_slash))
 		.with_child(
-			XmlNode::new("enclosure")
-				.with_attribute(
-					"url",
-					format!(
-						"https://music.astronomy487.com/artwork/{}.jpg",
-						image_name_without_slash
-					)
-				)
-				.with_attribute("length", {
-					let path = globals::filezone()
-						.join("music.astronomy487.com")
-						.join("artwork")
-						.join(image_name_without_slash)
-						.with_extension("jpg");
-					format!(
-						"{}",
-						crate::fileops::filesize(&path).unwrap_or_else(|| panic!(
-							"RSS could not find the promised artwork \"{}.jpg\" in music.astronomy487.com directory",
-							image_name_without_slash
-						))
-					)
-				})
-				.with_attribute("type", "image/jpeg")
+			XmlNode::new("pubDate")
+				.with_text(titlable.released().to_rfc822(titlable.release_time()))
 		)
-		.with_child(XmlNode::new("pubDate").with_text(titlable.released().to_rfc822()))
 		.with_child(XmlNode::new("media:title").with_text(titlable.format_title()))
 		.with_child(XmlNode::new("media:thumbnail").with_text(format!(
 			"https://music.astronomy487.com/{}.jpg",
 			image_name_without_slash
 		)))
 		.with_child(XmlNode::new("media:credit").with_text("Astro"))
-		.with_child(XmlNode::new("media:keywords").with_text("electronic music"));
+		.with_child(XmlNode::new("media:keywords").with_text("electronic music"))
+		// itunes only knows yes or no, so clean versions are "false"
+		.with_child(XmlNode::new("itunes:explicit").with_text(
+			if titlable.advisory().is_explicit() {
+				"true"
+			} else {
+				"false"
+			}
+		));
 	let download_filename = titlable.slug().to_owned()
 		+ match &titlable {
 			Titlable::Album(_) => "zip",
@@ -210,17 +200,42 @@ fn rss_item_for_titlable(titlable: Titlable) -> XmlNode {
 	}
 	item
 }
+// our own copy of the artwork, never someone else's server
+fn artwork_enclosure(image_name_without_slash: &str) -> XmlNode {
+	XmlNode::new("enclosure")
+		.with_attribute(
+			"url",
+			format!(
+				"https://music.astronomy487.com/artwork/{}.jpg",
+				image_name_without_slash
+			)
+		)
+		.with_attribute("length", {
+			let path = globals::filezone()
+				.join("music.astronomy487.com")
+				.join("artwork")
+				.join(image_name_without_slash)
+				.with_extension("jpg");
+			format!(
+				"{}",
+				crate::fileops::filesize(&path).unwrap_or_else(|| panic!(
+					"RSS could not find the promised artwork \"{}.jpg\" in music.astronomy487.com directory",
+					image_name_without_slash
+				))
+			)
+		})
+		.with_attribute("type", "image/jpeg")
+}
 fn rss_item_for_assist(assist: &Assist) -> XmlNode {
+	let link = format!("https://music.astronomy487.com/{}/", assist.slug);
 	XmlNode::new("item")
+		.with_child(XmlNode::new("title").with_text(assist.format_title()))
+		.with_child(XmlNode::new("link").with_text(&link))
 		.with_child(
-			XmlNode::new("title").with_text(format!("{} ({})", assist.titlable, assist.role))
-		)
-		.with_child(XmlNode::new("link").with_text(&assist.url))
-		/* .with_child(
 			XmlNode::new("guid")
 				.with_attribute("isPermaLink", "true")
-				.with_text(&assist.url)
-		) */
+				.with_text(link)
+		)
 		.with_child(
 			XmlNode::new("category")
 				.with_attribute("domain", "topic")
@@ -236,30 +251,18 @@ fn rss_item_for_assist(assist: &Assist) -> XmlNode {
 				.with_attribute("url", "https://music.astronomy487.com/rss.xml")
 				.with_text("Astro's discography")
 		)
+		.with_child(artwork_enclosure(&assist.artwork.name_without_slash))
 		.with_child(
-			XmlNode::new("enclosure")
-				.with_attribute("url", &assist.artwork)
-				.maybe_with_attribute("type", {
-					if assist.artwork.ends_with(".png") {
-						Some("image/png")
-					} else if assist.artwork.ends_with(".jpg") {
-						Some("image/jpeg")
-					} else {
-						globals::log_2(
-							"Warning",
-							format!(
-								"Cannot determine mime type of assist artwork {}",
-								assist.artwork
-							),
-							globals::ANSI_RED
-						);
-						None
-					}
-				})
+			XmlNode::new("pubDate").with_text(assist.released.to_rfc822(&ReleaseTime::default()))
 		)
-		.with_child(XmlNode::new("pubDate").with_text(assist.released.to_rfc822()))
 		.with_child(XmlNode::new("media:title").with_text(&assist.titlable))
-		.with_child(XmlNode::new("media:thumbnail").with_attribute("url", &assist.artwork))
+		.with_child(XmlNode::new("media:thumbnail").with_attribute(
+			"url",
+			format!(
+				"https://music.astronomy487.com/artwork/{}.jpg",
+				assist.artwork.name_without_slash
+			)
+		))
 		/* .with_child(
 			XmlNode::new("media:credit")
 				.with_text("Astro")
@@ -1,16 +1,20 @@
 use crate::globals;
 use crate::media::{album::Album, assist::Assist, song::Song};
 
-pub fn make_sitemap(all_albums: &[Album], all_remixes: &[Song], _all_assists: &[Assist]) {
+pub fn make_sitemap(all_albums: &[Album], all_remixes: &[Song], all_assists: &[Assist]) {
 	let mut list_of_urls: Vec<String> = Vec::new();
 	list_of_urls.push("https://music.astronomy487.com/".to_string());
 
-	for album in all_albums {
+	// nothing coming soon goes in; it'll show up once it's out
+	for album in all_albums
+		.iter()
+		.filter(|album| !album.unreleased && album.folded_into.is_none())
+	{
 		list_of_urls.push(format!("https://music.astronomy487.com/{}/", album.slug));
 		// list_of_urls.push(format!("https://audio.astronomy487.com/mp3/{}.zip", album.slug));
 		// list_of_urls.push(format!("https://audio.astronomy487.com/flac/{}.zip", album.slug));
 		for song in &album.songs {
-			if !song.bonus {
+			if !song.bonus && !song.unreleased {
 				if song.slug != album.slug {
 					list_of_urls.push(format!("https://music.astronomy487.com/{}/", song.slug));
 				}
@@ -32,7 +36,7 @@ pub fn make_sitemap(all_albums: &[Album], all_remixes: &[Song], _all_assists: &[
 			}
 		}
 	}
-	for remix in all_remixes {
+	for remix in all_remixes.iter().filter(|remix| !remix.unreleased) {
 		list_of_urls.push(format!("https://music.astronomy487.com/{}/", remix.slug));
 		if remix.lyrics.is_some() {
 			list_of_urls.push(format!(
@@ -50,6 +54,9 @@ pub fn make_sitemap(all_albums: &[Album], all_remixes: &[Song], _all_assists: &[
 		// list_of_urls.push(format!("https://audio.astronomy487.com/mp3/{}.mp3", remix.slug));
 		// list_of_urls.push(format!("https://audio.astronomy487.com/flac/{}.flac", remix.slug));
 	}
+	for assist in all_assists {
+		list_of_urls.push(format!("https://music.astronomy487.com/{}/", assist.slug));
+	}
 
 	list_of_urls.sort();
 
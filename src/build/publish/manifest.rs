@@ -0,0 +1,218 @@
+// private/publish-manifest.json remembers the slug of everything we last published
+// so that a changed artist or title can't quietly move a page out from under its old links
+
+use crate::fileops;
+use crate::globals;
+use crate::media::{album::Album, assist::Assist, song::Song, titlable::Titlable};
+
+struct Published {
+	kind: String,
+	slug: String,
+	title: String,
+	code: Option<String> // isrc for songs, upc for albums
+}
+
+impl Published {
+	fn to_json(&self) -> serde_json::Value {
+		serde_json::json!({
+			"kind": self.kind,
+			"slug": self.slug,
+			"title": self.title,
+			"code": self.code
+		})
+	}
+}
+
+fn manifest_location() -> std::path::PathBuf {
+	globals::filezone()
+		.join("private")
+		.join("publish-manifest")
+		.with_extension("json")
+}
+
+// every titlable that gets a link page
+fn published_titlables<'a>(all_albums: &'a [Album], all_remixes: &'a [Song]) -> Vec<Titlable<'a>> {
+	let mut items = Vec::new();
+	for album in all_albums {
+		items.push(Titlable::Album(album));
+		if !album.is_single() {
+			for song in &album.songs {
+				if !song.bonus {
+					items.push(Titlable::Song(song));
+				}
+			}
+		}
+	}
+	for remix in all_remixes {
+		items.push(Titlable::Song(remix));
+	}
+	items
+}
+
+fn describe(titlable: &Titlable) -> Published {
+	let (kind, code) = match titlable {
+		Titlable::Album(album) => ("album", album.upc.as_ref().map(|upc| upc.to_string())),
+		Titlable::Song(song) => ("song", song.isrc.as_ref().map(|isrc| isrc.as_dense()))
+	};
+	Published {
+		kind: kind.to_string(),
+		slug: titlable.slug().to_string(),
+		title: titlable.format_title(),
+		code
+	}
+}
+
+// everything that gets a page, assists included. assists have no code, so they're only matched by title
+fn published_items(
+	all_albums: &[Album], all_remixes: &[Song], all_assists: &[Assist]
+) -> Vec<Published> {
+	published_titlables(all_albums, all_remixes)
+		.iter()
+		.map(describe)
+		.chain(all_assists.iter().map(|assist| Published {
+			kind: String::from("assist"),
+			slug: assist.slug.clone(),
+			title: assist.format_title(),
+			code: None
+		}))
+		.collect()
+}
+
+// every slug something still answers to, old ones included
+fn answered_slugs(
+	all_albums: &[Album], all_remixes: &[Song], all_assists: &[Assist]
+) -> std::collections::HashSet<String> {
+	published_titlables(all_albums, all_remixes)
+		.iter()
+		.flat_map(|titlable| {
+			std::iter::once(titlable.slug().to_string())
+				.chain(titlable.previous_slugs().iter().cloned())
+		})
+		.chain(all_assists.iter().map(|assist| assist.slug.clone()))
+		.collect()
+}
+
+pub fn write_manifest(all_albums: &[Album], all_remixes: &[Song], all_assists: &[Assist]) {
+	// old entries nothing answers to stay, so the warning keeps coming up until it's dealt with
+	let still_answered = answered_slugs(all_albums, all_remixes, all_assists);
+	let entries: Vec<serde_json::Value> = published_items(all_albums, all_remixes, all_assists)
+		.iter()
+		.chain(
+			read_manifest()
+				.unwrap_or_default()
+				.iter()
+				.filter(|published| !still_answered.contains(&published.slug))
+		)
+		.map(Published::to_json)
+		.collect();
+	fileops::write_file(
+		&manifest_location(),
+		serde_json::to_string_pretty(&entries).expect("Couldn't serialize publish manifest")
+	);
+}
+
+fn read_manifest() -> Option<Vec<Published>> {
+	let text = std::fs::read_to_string(manifest_location()).ok()?;
+	let json: serde_json::Value = serde_json::from_str(&text)
+		.unwrap_or_else(|error| panic!("publish-manifest.json is invalid JSON: {}", error));
+	let field = |entry: &serde_json::Value, key: &str| -> Option<String> {
+		entry.get(key).and_then(|v| v.as_str()).map(str::to_string)
+	};
+	Some(
+		json.as_array()
+			.unwrap_or_else(|| panic!("publish-manifest.json is not an array"))
+			.iter()
+			.map(|entry| Published {
+				kind: field(entry, "kind").unwrap_or_else(|| {
+					panic!("publish-manifest.json entry has no kind: {}", entry)
+				}),
+				slug: field(entry, "slug").unwrap_or_else(|| {
+					panic!("publish-manifest.json entry has no slug: {}", entry)
+				}),
+				title: field(entry, "title").unwrap_or_else(|| {
+					panic!("publish-manifest.json entry has no title: {}", entry)
+				}),
+				code: field(entry, "code")
+			})
+			.collect()
+	)
+}
+
+fn levenshtein(from: &str, to: &str) -> usize {
+	let to_chars: Vec<char> = to.chars().collect();
+	let mut previous_row: Vec<usize> = (0..=to_chars.len()).collect();
+	for (i, from_char) in from.chars().enumerate() {
+		let mut row = vec![i + 1];
+		for (j, to_char) in to_chars.iter().enumerate() {
+			let substitution = previous_row[j] + usize::from(from_char != *to_char);
+			row.push(substitution.min(previous_row[j + 1] + 1).min(row[j] + 1));
+		}
+		previous_row = row;
+	}
+	previous_row[to_chars.len()]
+}
+
+// 1.0 for identical titles, 0.0 for nothing in common
+fn title_similarity(title: &str, other_title: &str) -> f64 {
+	let longest = title.chars().count().max(other_title.chars().count());
+	if longest == 0 {
+		return 1.0;
+	}
+	1.0 - levenshtein(&title.to_lowercase(), &other_title.to_lowercase()) as f64 / longest as f64
+}
+
+const SIMILAR_ENOUGH: f64 = 0.6;
+
+// warns about every published slug that nothing answers to anymore
+pub fn check_against_manifest(all_albums: &[Album], all_remixes: &[Song], all_assists: &[Assist]) {
+	let Some(manifest) = read_manifest() else {
+		return;
+	};
+	let current = published_items(all_albums, all_remixes, all_assists);
+	let still_answered = answered_slugs(all_albums, all_remixes, all_assists);
+	for published in &manifest {
+		if still_answered.contains(&published.slug) {
+			continue;
+		}
+		// same isrc/upc is a sure thing; otherwise go by the closest title among new slugs
+		let candidates = current
+			.iter()
+			.filter(|item| item.kind == published.kind)
+			.filter(|item| !manifest.iter().any(|old| old.slug == item.slug));
+		let by_code = candidates
+			.clone()
+			.find(|item| published.code.is_some() && item.code == published.code);
+		let by_title = candidates
+			.map(|item| (item, title_similarity(&item.title, &published.title)))
+			.filter(|(_, similarity)| *similarity >= SIMILAR_ENOUGH)
+			.max_by(|(_, similarity), (_, other)| similarity.total_cmp(other))
+			.map(|(item, _)| item);
+		match by_code.or(by_title) {
+			Some(renamed) => globals::log_2(
+				"Warning",
+				format!(
+					"{} used to be at /{}/ but is now at /{}/. add \"previous_slugs\": [\"{}\"] to keep the old links working, or pin \"slug\": \"{}\" to keep the old slug",
+					renamed.title, published.slug, renamed.slug, published.slug, published.slug
+				),
+				globals::ANSI_RED
+			),
+			None => globals::log_2(
+				"Warning",
+				format!(
+					"{} {} was published at /{}/, but nothing is there anymore",
+					published.kind, published.title, published.slug
+				),
+				globals::ANSI_RED
+			)
+		}
+	}
+}
+
+#[test]
+fn similar_titles_are_close() {
+	assert_eq!(levenshtein("kitten", "sitting"), 3);
+	assert_eq!(levenshtein("", "abc"), 3);
+	assert_eq!(levenshtein("same", "same"), 0);
+	assert!(title_similarity("Astro – Nightcall", "Astro – Night Call") >= SIMILAR_ENOUGH);
+	assert!(title_similarity("Astro – Rosalie", "Someone Else – Nightcall") < SIMILAR_ENOUGH);
+}
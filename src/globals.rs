@@ -63,7 +63,7 @@ pub fn ask_to_continue() -> bool {
 }
 
 pub fn map_with_only_these_keys<'a>(
-	val: &'a serde_json::Value, label: &'static str, allowed: &'static [&'static str]
+	val: &'a serde_json::Value, label: &'static str, allowed: &[&str]
 ) -> &'a serde_json::Map<String, serde_json::Value> {
 	let obj = val
 		.as_object()
@@ -84,11 +84,11 @@ pub fn map_with_only_these_keys<'a>(
 	obj
 }
 
-pub fn compute_slug(artist: &str, title: &str) -> String {
-	let mut slug = if artist == "Astro" {
+pub fn compute_slug(artists: &[String], title: &str) -> String {
+	let mut slug = if artists == ["Astro"] {
 		title.to_owned()
 	} else {
-		format!("{} {}", artist, title)
+		format!("{} {}", artists.join(" "), title)
 	};
 	slug = slug.to_lowercase();
 	slug = unicode_normalization::UnicodeNormalization::nfd(slug.chars())
@@ -114,8 +114,8 @@ pub fn compute_slug(artist: &str, title: &str) -> String {
 	assert!(
 		slug.chars()
 			.all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-'),
-		"Could not generate a valid slug for artist {} and title {}; we arrived at {}",
-		artist,
+		"Could not generate a valid slug for artists {:?} and title {}; we arrived at {}",
+		artists,
 		title,
 		slug
 	);
@@ -126,11 +126,105 @@ pub fn compute_slug(artist: &str, title: &str) -> String {
 fn slugs_are_right() {
 	assert_eq!(
 		compute_slug(
-			"underscores",
+			&[String::from("underscores")],
 			"Girls and boys—but secretly, you'd love to know what it's like, wouldn't you?"
 		),
 		"underscores-girls-and-boys-but-secretly-youd-love-to-know-what-its-like-wouldnt-you"
-	)
+	);
+	assert_eq!(
+		compute_slug(&[String::from("Astro"), String::from("Friend")], "Duet"),
+		"astro-friend-duet"
+	);
+}
+
+// "artist" in discog.json: missing (Astro), one entry, or an array of entries.
+// an entry is a string, or {"name": ..., "one_artist": true} for a name that really has "and" in it
+pub fn parse_artists(maybe_val: Option<&serde_json::Value>, what: &str) -> Vec<String> {
+	let parse_entry = |artist_val: &serde_json::Value| -> (String, bool) {
+		match artist_val {
+			serde_json::Value::String(artist) => (artist.to_string(), false),
+			serde_json::Value::Object(_) => {
+				let obj = map_with_only_these_keys(
+					artist_val,
+					"Artist",
+					&crate::media::schema::keys(crate::media::schema::ARTIST)
+				);
+				let name = obj.get("name").and_then(|v| v.as_str()).unwrap_or_else(|| {
+					panic!(
+						"{} JSON artist has no string \"name\": {}",
+						what, artist_val
+					)
+				});
+				let one_artist = obj.get("one_artist").is_some_and(|v| {
+					v.as_bool().unwrap_or_else(|| {
+						panic!(
+							"{} JSON artist \"one_artist\" is not a boolean: {}",
+							what, artist_val
+						)
+					})
+				});
+				(name.to_string(), one_artist)
+			}
+			other => panic!(
+				"{} JSON has an artist that isn't a string or an object: {}",
+				what, other
+			)
+		}
+	};
+	let entries: Vec<(String, bool)> = match maybe_val {
+		None => vec![(String::from("Astro"), false)],
+		Some(serde_json::Value::Array(array)) => array.iter().map(parse_entry).collect(),
+		Some(artist_val) => vec![parse_entry(artist_val)]
+	};
+	assert!(
+		!entries.is_empty(),
+		"{} JSON has an empty \"artist\" list",
+		what
+	);
+	for (artist, one_artist) in &entries {
+		assert!(
+			!artist.is_empty()
+				&& !artist.starts_with(char::is_whitespace)
+				&& !artist.ends_with(char::is_whitespace),
+			"{} JSON has poorly formed \"artist\" string: {}",
+			what,
+			artist
+		);
+		// several people go in several entries, so they all get joined the same way
+		assert!(
+			!artist.contains('&')
+				&& !artist.contains(',')
+				&& (*one_artist
+					|| !artist
+						.split_whitespace()
+						.any(|word| word.eq_ignore_ascii_case("and"))),
+			"{} artist \"{}\" looks like more than one artist; give \"artist\" as an array instead (or {{\"name\": \"{}\", \"one_artist\": true}} if it really is one)",
+			what,
+			artist,
+			artist
+		);
+	}
+	entries.into_iter().map(|(artist, _)| artist).collect()
+}
+
+#[test]
+fn artists_with_and_need_one_artist() {
+	assert_eq!(
+		parse_artists(
+			Some(&serde_json::json!([
+				"Astro",
+				{"name": "Barbie as The Princess and The Pauper", "one_artist": true}
+			])),
+			"Test"
+		),
+		vec!["Astro", "Barbie as The Princess and The Pauper"]
+	);
+}
+
+#[test]
+#[should_panic(expected = "looks like more than one artist")]
+fn artists_with_and_are_rejected() {
+	let _ = parse_artists(Some(&serde_json::json!("Astro and Friend")), "Test");
 }
 
 pub fn check_custom_slug(slug: &str) {
@@ -142,7 +236,35 @@ pub fn check_custom_slug(slug: &str) {
 	);
 }
 
+// old slugs an item used to live at, so the build can redirect them
+pub fn parse_previous_slugs(maybe_val: Option<&serde_json::Value>, what: &str) -> Vec<String> {
+	match maybe_val {
+		None => Vec::new(),
+		Some(serde_json::Value::Array(arr)) => arr
+			.iter()
+			.map(|slug_val| {
+				let slug = slug_val.as_str().unwrap_or_else(|| {
+					panic!(
+						"{} JSON has non-string slug in \"previous_slugs\": {}",
+						what, slug_val
+					)
+				});
+				check_custom_slug(slug);
+				slug.to_string()
+			})
+			.collect(),
+		Some(other) => panic!(
+			"{} JSON attribute \"previous_slugs\" must be an array of strings: {}",
+			what, other
+		)
+	}
+}
+
 pub const OG_KEYWORDS: &str = "electronic, dance, music, astro, artist, indie, edm";
 pub const OG_AUTHOR: &str = "Astro, astronomy487";
 pub const OG_ROBOTS: &str = "index, follow";
 pub const OG_SITE_NAME: &str = "astronomy487.com";
+pub const LYRICS_AUTHOR: &str = "astronomy487"; // [by:] in lrc files
+pub const DEFAULT_RELEASE_TIME: &str = "17:00"; // HH:MM, for anything without "release_time"
+pub const DEFAULT_TIMEZONE: &str = "+00:00"; // utc offset, for anything without "timezone"
+pub const ARTIST_SEPARATOR: &str = ", "; // between artists whenever there's more than one
@@ -24,13 +24,20 @@ fn main() {
 	let runtime = std::time::SystemTime::now();
 
 	set_panic_hook();
+	let args: Vec<String> = std::env::args().skip(1).collect();
+	// the schema doesn't need any tools or source files
+	if args.len() == 1 && args[0] == "schema" {
+		return crate::media::schema::distri_schema();
+	}
 	check_if_can_run();
 
-	let args: Vec<String> = std::env::args().skip(1).collect();
 	if args.is_empty() {
 		distri_help();
 		return;
 	}
+	if args[0] == "lyrics" {
+		return crate::media::lyrictools::distri_lyrics(&args[1..]);
+	}
 	let allowed = ["publish", "validate", "encode", "build", "clean"];
 	if args.iter().any(|a| !allowed.contains(&a.as_str())) {
 		return distri_help();
@@ -45,7 +52,7 @@ fn main() {
 		let build = args.contains(&"build".into());
 		let valid = args.contains(&"validate".into());
 		if encode || build || valid {
-			distri_encode(encode, build);
+			let _ = distri_encode(encode, build);
 		} else {
 			return distri_help();
 		}
@@ -63,7 +70,14 @@ fn main() {
 		);
 	}
 }
-fn distri_encode(build_r2_bucket: bool, build_static_website: bool) {
+// hands the music data back so publishing can remember what went out
+fn distri_encode(
+	build_r2_bucket: bool, build_static_website: bool
+) -> (
+	Vec<crate::media::album::Album>,
+	Vec<crate::media::song::Song>,
+	Vec<crate::media::assist::Assist>
+) {
 	let just_validating = !build_r2_bucket && !build_static_website;
 
 	let json_location = globals::filezone()
@@ -72,6 +86,11 @@ fn distri_encode(build_r2_bucket: bool, build_static_website: bool) {
 		.with_extension("json");
 
 	let (all_albums, all_remixes, all_assists) = crate::media::get_music_data(&json_location);
+	crate::build::publish::manifest::check_against_manifest(
+		&all_albums,
+		&all_remixes,
+		&all_assists
+	);
 
 	if build_r2_bucket {
 		for album in &all_albums {
@@ -95,13 +114,17 @@ fn distri_encode(build_r2_bucket: bool, build_static_website: bool) {
 		crate::build::pages::homepage::make_home_page(&all_albums, &all_remixes, &all_assists);
 
 		globals::log_2("Building", "Link pages", globals::ANSI_BLUE);
-		for album in &all_albums {
+		// folded temporary singles get redirected to their album track instead (see redirects.rs)
+		for album in all_albums
+			.iter()
+			.filter(|album| album.folded_into.is_none())
+		{
 			crate::build::pages::linkpage::make_link_page(
 				&crate::media::titlable::Titlable::Album(album),
 				&all_albums,
 				build_r2_bucket
 			);
-			if !album.single {
+			if !album.is_single() {
 				for song in &album.songs {
 					if !song.bonus {
 						crate::build::pages::linkpage::make_link_page(
@@ -120,6 +143,12 @@ fn distri_encode(build_r2_bucket: bool, build_static_website: bool) {
 				build_r2_bucket
 			);
 		}
+		for assist in &all_assists {
+			crate::build::pages::assistpage::make_assist_page(assist);
+		}
+
+		globals::log_3("Building", "", "Redirects", globals::ANSI_BLUE);
+		crate::build::pages::redirects::make_redirects(&all_albums, &all_remixes);
 
 		globals::log_3("Building", "", "Other web assets", globals::ANSI_BLUE);
 		// album art jpgs
@@ -144,6 +173,9 @@ fn distri_encode(build_r2_bucket: bool, build_static_website: bool) {
 					let _ = artwork_that_needs_copying.insert(art.clone());
 				}
 			}
+			for assist in &all_assists {
+				let _ = artwork_that_needs_copying.insert(assist.artwork.clone());
+			}
 			let dest_dir = globals::filezone()
 				.join("music.astronomy487.com")
 				.join("artwork");
@@ -280,8 +312,24 @@ fn distri_encode(build_r2_bucket: bool, build_static_website: bool) {
 	}
 
 	if just_validating {
+		for song in all_albums
+			.iter()
+			.flat_map(|album| album.songs.iter())
+			.chain(all_remixes.iter())
+		{
+			if let Some(lyrics) = &song.lyrics {
+				globals::log_3(
+					"Lyrics",
+					format!("{:.0}%", lyrics.coverage() * 100.0),
+					song.format_title(),
+					globals::ANSI_GREEN
+				);
+			}
+		}
 		println!("Validation was successful");
 	}
+
+	(all_albums, all_remixes, all_assists)
 }
 
 fn distri_publish() {
@@ -329,7 +377,7 @@ fn distri_publish() {
 
 	println!("This will publish content to the internet.");
 	if globals::ask_to_continue() {
-		distri_encode(true, true);
+		let (all_albums, all_remixes, all_assists) = distri_encode(true, true);
 		crate::build::publish::wrangle::music_astronomy487_com();
 		// wrangler can be talkative - delete its extra directories
 		for dir_name in [".wrangler", "node_modules"] {
@@ -341,6 +389,7 @@ fn distri_publish() {
 			}
 		}
 		crate::build::publish::rclone::audio_astronomy487_com();
+		crate::build::publish::manifest::write_manifest(&all_albums, &all_remixes, &all_assists);
 	}
 }
 
@@ -414,6 +463,16 @@ fn distri_help() {
 			"publish",
 			"Encode, build, and publish content to Cloudflare R2 and Pages.",
 			globals::ANSI_PURPLE
+		),
+		(
+			"lyrics",
+			"Import lyrics from lrc/srt/vtt, or retime them: lyrics import|retime ...",
+			globals::ANSI_GREEN
+		),
+		(
+			"schema",
+			"Write a JSON Schema for discog.json to source/discog.schema.json.",
+			globals::ANSI_GREEN
 		)
 	] {
 		println!(
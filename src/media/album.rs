@@ -2,57 +2,50 @@ use crate::build::smartquotes;
 use crate::fileops;
 use crate::globals;
 use crate::media::{
-	artwork::Artwork, audiocodec::AudioCodec, lyric::TextCodec, song::Song, titlable::Titlable
+	artwork::Artwork, audiocodec::AudioCodec, credit, credit::Credit, lyric::TextCodec, schema,
+	song::Song, titlable::Titlable, vocalist::VocalistRegistry
 };
 use crate::types::{
-	color::Palette, date::Date, duration::Duration, genre::Genre, upc::UPC, urlset::UrlSet
+	advisory::Advisory,
+	color::Palette,
+	date::{Date, ReleaseTime},
+	duration::Duration,
+	genre::Genre,
+	releasetype::ReleaseType,
+	upc::UPC,
+	urlset::UrlSet
 };
 
 #[derive(Debug)]
 pub struct Album {
 	pub slug: String,
+	pub previous_slugs: Vec<String>, // each one gets redirected to slug
 	pub songs: Vec<Song>,
 	pub title: String,
-	pub artist: String,
+	pub artist: String, // every artist, joined for display
+	pub artists: Vec<String>,
 	pub released: Date,
+	pub release_time: ReleaseTime,
 	pub duration: Duration,
 	pub upc: Option<UPC>,
 	pub bcid: Option<String>,
 	pub about: Option<Vec<String>>,
 	pub palette: Palette,
-	pub single: bool,
-	pub compilation: bool,
+	pub release_type: ReleaseType,
+	pub temporary: Option<String>, // slug of the album track this single becomes
+	pub folded_into: Option<(usize, usize)>, // that track (album-index, position), once its album is out
 	pub url: UrlSet,
 	pub genre: Genre,
 	pub unreleased: bool,
 	pub discs: Option<Vec<(usize, String)>>,
+	pub credits: Vec<Credit>, // apply to every song on the album
 	pub artwork: Artwork,
 	pub has_8831: bool
 }
 
 impl Album {
-	pub fn from_json(val: &serde_json::Value) -> Album {
-		let obj = globals::map_with_only_these_keys(
-			val,
-			"Album",
-			&[
-				"about",
-				"bcid",
-				"color",
-				"discs",
-				"genre",
-				"released",
-				"songs",
-				"title",
-				"upc",
-				"url",
-				"compilation",
-				"artist",
-				"single",
-				"unreleased",
-				"slug"
-			]
-		);
+	pub fn from_json(val: &serde_json::Value, vocalists: &VocalistRegistry) -> Album {
+		let obj = globals::map_with_only_these_keys(val, "Album", &schema::keys(schema::ALBUM));
 		let url_set = {
 			let url_val = obj
 				.get("url")
@@ -61,24 +54,8 @@ impl Album {
 			UrlSet::from(url_val)
 		};
 
-		let artist = match obj.get("artist") {
-			None => "Astro".to_string(),
-			Some(artist_val) => {
-				let artist = artist_val.as_str().unwrap_or_else(|| {
-					panic!(
-						"Album JSON has non-string \"artist\" attribute: {}",
-						artist_val
-					)
-				});
-				assert!(
-					!artist.starts_with(char::is_whitespace)
-						&& !artist.ends_with(char::is_whitespace),
-					"Album JSON has poorly formed \"artist\" string: {}",
-					artist
-				);
-				artist.to_string()
-			}
-		};
+		let artists = globals::parse_artists(obj.get("artist"), "Album");
+		let artist = artists.join(globals::ARTIST_SEPARATOR);
 		let title = {
 			let title_object = obj
 				.get("title")
@@ -102,11 +79,17 @@ impl Album {
 				string.to_string()
 			}
 			Some(other) => panic!("Custom slug \"{}\" is not a string", other),
-			None => globals::compute_slug(&artist, &title)
+			None => globals::compute_slug(&artists, &title)
 		};
 
+		let credits = obj
+			.get("credits")
+			.map(|credits_json| Credit::list_from_json(credits_json, &title))
+			.unwrap_or_default();
+
 		let mut album = Album {
 			songs: Vec::new(),
+			previous_slugs: globals::parse_previous_slugs(obj.get("previous_slugs"), "Album"),
 			artwork: Artwork::from(Some(&slug), &slug),
 			has_8831: {
 				let location = globals::filezone()
@@ -134,6 +117,7 @@ impl Album {
 			},
 			slug,
 			artist,
+			artists,
 			title,
 			released: {
 				let rel_val = obj
@@ -159,6 +143,7 @@ impl Album {
 				});
 				Genre::from(genre_str)
 			},
+			release_time: ReleaseTime::from_json(obj, None, "Album"),
 			duration: Duration::zero(), // later filled via songs
 			unreleased: match obj.get("unreleased") {
 				None => false,
@@ -169,23 +154,33 @@ impl Album {
 					)
 				})
 			},
-			single: match obj.get("single") {
-				None => false,
-				Some(val_for_single) => val_for_single.as_bool().unwrap_or_else(|| {
+			temporary: obj.get("temporary").map(|val_for_temporary| {
+				let target = val_for_temporary.as_str().unwrap_or_else(|| {
 					panic!(
-						"Album JSON attribute \"single\" is not a boolean: {}",
-						val_for_single
+						"Album JSON attribute \"temporary\" must be the slug of the album track it becomes: {}",
+						val_for_temporary
 					)
-				})
-			},
-			compilation: match obj.get("compilation") {
-				None => false,
-				Some(val_for_compilation) => val_for_compilation.as_bool().unwrap_or_else(|| {
-					panic!(
-						"Album JSON attribute \"compilation\" is not a boolean: {}",
-						val_for_compilation
-					)
-				})
+				});
+				globals::check_custom_slug(target);
+				target.to_string()
+			}),
+			folded_into: None, // filled in once every album is known
+			release_type: match obj.get("release_type") {
+				None => ReleaseType::Album,
+				Some(val_for_release_type) => {
+					let release_type =
+						ReleaseType::from(val_for_release_type.as_str().unwrap_or_else(|| {
+							panic!(
+								"Album JSON attribute \"release_type\" is not a string: {}",
+								val_for_release_type
+							)
+						}));
+					assert!(
+						release_type.allowed_for_albums(),
+						"Album JSON can't have release type \"remix\"; remixes go in discog.remixes"
+					);
+					release_type
+				}
 			},
 			upc: obj.get("upc").map(|v| {
 				let upc = v.as_str().unwrap_or_else(|| {
@@ -230,6 +225,7 @@ impl Album {
 					})
 					.collect()
 			}),
+			credits,
 			palette: Palette::from(
 				obj.get("color")
 					.unwrap_or_else(|| panic!("Album JSON {} has no attribute \"color\"", val)),
@@ -260,6 +256,10 @@ impl Album {
 		};
 		assert!(!smartquotes::contains_smart_quotes(&album.title));
 		assert!(!smartquotes::contains_smart_quotes(&album.artist));
+		// scheduled releases come out on their own, on the first build after their release_time in their timezone
+		if !album.release_time.has_arrived(&album.released) {
+			album.unreleased = true;
+		}
 		{
 			let songs_val = obj
 				.get("songs")
@@ -271,7 +271,9 @@ impl Album {
 				)
 			});
 			for song_json in songs_arr.iter() {
-				album.songs.push(Song::from_json(song_json, Some(&album)));
+				album
+					.songs
+					.push(Song::from_json(song_json, Some(&album), vocalists));
 			}
 			album.duration =
 				Duration::accumulate(album.songs.iter().filter(|s| !s.bonus).map(|s| s.duration));
@@ -290,15 +292,37 @@ impl Album {
 		Titlable::Album(self).public_filename()
 	}
 	pub fn try_encode(&self, all_albums: &[Album]) {
+		if let Some((album_index, song_index)) = self.folded_into {
+			// the album track has the audio now, so this single's files go away
+			let track = &all_albums[album_index].songs[song_index];
+			for codec in [AudioCodec::Mp3, AudioCodec::Flac] {
+				for stale in [
+					self.songs[0].destination_location(&codec),
+					globals::filezone()
+						.join("audio.astronomy487.com")
+						.join(codec.ext())
+						.join(self.public_filename())
+						.with_extension("zip")
+				] {
+					// unless the track shares the single's slug and serves that very file
+					if stale.exists() && stale != track.destination_location(&codec) {
+						globals::log_3("Removing", codec.ext(), stale.display(), globals::ANSI_RED);
+						std::fs::remove_file(&stale)
+							.unwrap_or_else(|_| panic!("Couldn't remove {}", stale.display()));
+					}
+				}
+			}
+			return;
+		}
 		for song in &self.songs {
 			song.try_encode(all_albums);
 		}
 		if !self.unreleased {
-			self.zip(&AudioCodec::Mp3);
-			self.zip(&AudioCodec::Flac);
+			self.zip(&AudioCodec::Mp3, all_albums);
+			self.zip(&AudioCodec::Flac, all_albums);
 		}
 	}
-	fn zip(&self, codec: &AudioCodec) {
+	fn zip(&self, codec: &AudioCodec, all_albums: &[Album]) {
 		let destination = globals::filezone()
 			.join("audio.astronomy487.com")
 			.join(codec.ext())
@@ -338,7 +362,7 @@ impl Album {
 			if let Some(lyrics) = &song.lyrics {
 				// only include .txt - don't bother with lrc or srt in zips
 				zipper.add_text_file(
-					&lyrics.as_filetype(TextCodec::Txt),
+					&lyrics.as_filetype(TextCodec::Txt, &song.lyric_context(all_albums)),
 					std::path::Path::new(&format!(
 						"lyrics/{} {}.txt",
 						pad_digits(self.songs.len(), song_index + 1),
@@ -354,6 +378,13 @@ impl Album {
 		);
 		zipper.finish();
 	}
+	// the download includes bonus tracks, so they count too
+	pub fn advisory(&self) -> Advisory {
+		Advisory::of_all(self.songs.iter().map(|song| song.explicit))
+	}
+	pub fn is_single(&self) -> bool {
+		self.release_type == ReleaseType::Single
+	}
 	pub fn non_bonus_song_count(&self) -> usize {
 		self.songs.iter().take_while(|song| !song.bonus).count()
 	}
@@ -409,6 +440,19 @@ impl Album {
 				}
 			}
 		}
+		let mut credit_lines = credit::describe(&self.credits);
+		for song in &self.songs {
+			for line in credit::describe(&song.credits) {
+				credit_lines.push(format!("{}: {}", song.title, line));
+			}
+		}
+		if let Some(artwork_credit) = &self.artwork.credit {
+			credit_lines.push(format!("Cover art by {}", artwork_credit));
+		}
+		if !credit_lines.is_empty() {
+			text.push(String::new());
+			text.extend(credit_lines);
+		}
 		text.push(String::new());
 		text.push(self.copyright_message_c_line());
 		text.push(self.copyright_message_p_line());
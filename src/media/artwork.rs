@@ -6,7 +6,8 @@ pub struct Artwork {
 	pub name_without_slash: String,      // used to communicate
 	pub source_path: std::path::PathBuf, // png stays here always
 	pub jpg_path: std::path::PathBuf, // as kept in private/jpg. this is where encode and build looks for them
-	pub caption: String
+	pub caption: String,
+	pub credit: Option<String> // who made it, from an optional .credit.txt next to the png
 }
 
 impl PartialEq for Artwork {
@@ -80,12 +81,27 @@ impl Artwork {
 			10
 		);
 
+		let credit_path = globals::filezone()
+			.join("source")
+			.join("artwork")
+			.join(&name_with_slash)
+			.with_extension("credit.txt");
+		let credit = std::fs::read_to_string(credit_path).ok().map(|credit| {
+			assert!(
+				!credit.is_empty() && credit.trim() == credit && !credit.contains(['\r', '\n']),
+				"Image credit for {}.png must be one trimmed line",
+				name_with_slash
+			);
+			credit
+		});
+
 		Self {
 			source_path,
 			jpg_path,
 			name_with_slash,
 			name_without_slash: artwork_name.to_string(),
-			caption
+			caption,
+			credit
 		}
 	}
 	pub fn make_jpg_exist(&self) {
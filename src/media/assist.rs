@@ -1,109 +1,75 @@
 use crate::build::smartquotes;
-use crate::types::date::Date;
+use crate::globals;
+use crate::media::{artwork::Artwork, schema};
+use crate::types::{assistrole::AssistRole, color::Palette, date::Date, urlset::UrlSet};
 
 #[derive(Debug)]
 pub struct Assist {
-	pub titlable: String,
+	pub slug: String,
+	pub titlable: String, // someone else's release, as free text
 	pub released: Date,
-	pub artwork: String,
-	pub url: String,
-	pub role: String
+	pub artwork: Artwork, // source/artwork/assists/slug.png
+	pub palette: Palette,
+	pub url: String, // where the release actually lives
+	pub role: AssistRole
 }
 impl Assist {
 	pub fn from_json(val: &serde_json::Value) -> Assist {
-		let assist = Assist {
-			titlable: val
-				.get("titlable")
-				.unwrap_or_else(|| panic!("Assists JSON has no \"titlable\" attribute: {}", val))
-				.as_str()
-				.unwrap_or_else(|| {
-					panic!(
-						"Assists JSON has non-string \"titlable\" attribute: {}",
-						val
-					)
-				})
-				.to_string(),
-			artwork: val
-				.get("artwork")
-				.unwrap_or_else(|| panic!("Assists JSON has no \"artwork\" attribute: {}", val))
-				.as_str()
-				.unwrap_or_else(|| {
-					panic!("Assists JSON has non-string \"artwork\" attribute: {}", val)
-				})
-				.to_string(),
-			url: val
-				.get("url")
-				.unwrap_or_else(|| panic!("Assists JSON has no \"url\" attribute: {}", val))
-				.as_str()
-				.unwrap_or_else(|| panic!("Assists JSON has non-string \"url\" attribute: {}", val))
-				.to_string(),
-			role: val
-				.get("role")
-				.unwrap_or_else(|| panic!("Assists JSON has no \"role\" attribute: {}", val))
+		let obj = globals::map_with_only_these_keys(val, "Assist", &schema::keys(schema::ASSIST));
+		let get_string = |key: &str| {
+			let string = obj
+				.get(key)
+				.unwrap_or_else(|| panic!("Assists JSON has no \"{}\" attribute: {}", key, val))
 				.as_str()
 				.unwrap_or_else(|| {
-					panic!("Assists JSON has non-string \"role\" attribute: {}", val)
-				})
-				.to_string(),
-			released: Date::from(
-				val.get("released")
-					.unwrap_or_else(|| {
-						panic!("Assists JSON has no \"released\" attribute: {}", val)
-					})
-					.as_str()
-					.unwrap_or_else(|| {
-						panic!(
-							"Assists JSON has non-string \"released\" attribute: {}",
-							val
-						)
-					})
-			)
+					panic!("Assists JSON has non-string \"{}\" attribute: {}", key, val)
+				});
+			assert!(
+				string.trim() == string,
+				"assist.{} has leading/trailing whitespace: '{}'",
+				key,
+				string
+			);
+			string.to_string()
 		};
-		// whitespace
-		assert!(
-			assist.titlable.trim() == assist.titlable,
-			"assist.titlable has leading/trailing whitespace: '{}'",
-			assist.titlable
-		);
-		assert!(
-			assist.artwork.trim() == assist.artwork,
-			"assist.artwork has leading/trailing whitespace: '{}'",
-			assist.artwork
-		);
+		let titlable = get_string("titlable");
+		let url = get_string("url");
 		assert!(
-			assist.url.trim() == assist.url,
-			"assist.url has leading/trailing whitespace: '{}'",
-			assist.url
+			url.starts_with("https://") && !url.contains(char::is_whitespace),
+			"assist.url is not a valid url: '{}'",
+			url
 		);
-		assert!(
-			assist.role.trim() == assist.role,
-			"assist.role has leading/trailing whitespace: '{}'",
-			assist.role
-		);
-
-		// artwork validation
-		let artwork = &assist.artwork;
-		let valid_prefix = artwork.starts_with("https://");
-		let valid_suffix = artwork.ends_with(".jpg") || artwork.ends_with(".png");
-		assert!(
-			valid_prefix && valid_suffix,
-			"assist.artwork must start with http(s):// and end with .jpg or .png: '{}'",
-			artwork
-		);
-
-		// "role" text validation
-		if let Some(first_char) = assist.role.chars().next() {
-			assert!(
-				first_char.to_uppercase().to_string() == first_char.to_string(),
-				"assist.role must start with an uppercase character: '{}'",
-				assist.role
-			);
-		} else {
-			panic!("assist.role is empty");
-		}
+		let slug = match obj.get("slug") {
+			Some(serde_json::Value::String(string)) => {
+				globals::check_custom_slug(string);
+				string.to_string()
+			}
+			Some(other) => panic!("Custom slug \"{}\" is not a string", other),
+			None => globals::compute_slug(&[String::from("Astro")], &titlable)
+		};
+		let assist = Assist {
+			artwork: Artwork::from(Some("assists"), &slug),
+			// assists without their own colors look like the home page
+			palette: Palette::from(
+				obj.get("color").unwrap_or(&serde_json::json!({
+					"foreground": "#ffffff",
+					"background": "#000000",
+					"accent": "#ffff00"
+				})),
+				&UrlSet::empty()
+			),
+			role: AssistRole::from(&get_string("role")),
+			released: Date::from(&get_string("released")),
+			slug,
+			titlable,
+			url
+		};
 
 		assert!(!smartquotes::contains_smart_quotes(&assist.titlable));
 
 		assist
 	}
+	pub fn format_title(&self) -> String {
+		format!("{} ({})", self.titlable, self.role.to_string())
+	}
 }
@@ -0,0 +1,143 @@
+use crate::globals;
+use crate::media::schema;
+
+macro_rules! define_roles {
+	(
+		$(
+			$text:expr, $name:ident, $label:expr ;
+		)*
+	) => {
+		#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+		pub enum Role {
+			$($name),*
+		}
+		impl Role {
+			pub const ALL: &[Role] = &[$(Role::$name),*];
+			pub fn to_string(self) -> &'static str {
+				match self {
+					$(Role::$name => $text),*
+				}
+			}
+			// how it reads on link pages and in zip readmes, before the names
+			pub fn label(self) -> &'static str {
+				match self {
+					$(Role::$name => $label),*
+				}
+			}
+			pub fn from(s: &str) -> Role {
+				match s {
+					$(
+						$text => Role::$name,
+					)*
+					_ => panic!("Unrecognized credit role \"{}\"", s),
+				}
+			}
+		}
+	};
+}
+
+// vocals aren't here on purpose; those come from the vocalist: tags in lyrics
+define_roles!(
+	"composer", Composer, "Written by";
+	"lyricist", Lyricist, "Lyrics by";
+	"producer", Producer, "Produced by";
+	"remixer", Remixer, "Remixed by";
+	"mixer", Mixer, "Mixed by";
+	"engineer", Engineer, "Engineered by";
+	"featured", Featured, "Featuring";
+);
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Credit {
+	pub role: Role,
+	pub name: String
+}
+
+impl Credit {
+	fn from_json(val: &serde_json::Value) -> Credit {
+		let obj = globals::map_with_only_these_keys(val, "Credit", &schema::keys(schema::CREDIT));
+		let role = Role::from(
+			obj.get("role")
+				.unwrap_or_else(|| panic!("Credit {} has no role", val))
+				.as_str()
+				.unwrap_or_else(|| panic!("Credit {} role is not a string", val))
+		);
+		let name = obj
+			.get("name")
+			.unwrap_or_else(|| panic!("Credit {} has no name", val))
+			.as_str()
+			.unwrap_or_else(|| panic!("Credit {} name is not a string", val))
+			.to_string();
+		assert!(
+			!name.is_empty()
+				&& !name.starts_with(char::is_whitespace)
+				&& !name.ends_with(char::is_whitespace),
+			"Credit name \"{}\" is empty or has untrimmed whitespace",
+			name
+		);
+		Credit { role, name }
+	}
+	pub fn list_from_json(val: &serde_json::Value, what: &str) -> Vec<Credit> {
+		let credits: Vec<Credit> = val
+			.as_array()
+			.unwrap_or_else(|| panic!("{} \"credits\" attribute is not an array", what))
+			.iter()
+			.map(Credit::from_json)
+			.collect();
+		for (index, credit) in credits.iter().enumerate() {
+			assert!(
+				!credits[..index].contains(credit),
+				"{} credits {} as {} twice",
+				what,
+				credit.name,
+				credit.role.to_string()
+			);
+		}
+		credits
+	}
+}
+
+// every name with this role, in the order they were given
+pub fn names_for(credits: &[Credit], role: Role) -> Vec<&str> {
+	credits
+		.iter()
+		.filter(|credit| credit.role == role)
+		.map(|credit| credit.name.as_str())
+		.collect()
+}
+
+// "Produced by A, B" for every role that has anyone
+pub fn describe(credits: &[Credit]) -> Vec<String> {
+	Role::ALL
+		.iter()
+		.filter_map(|role| {
+			let names = names_for(credits, *role);
+			(!names.is_empty()).then(|| format!("{} {}", role.label(), join_names(&names)))
+		})
+		.collect()
+}
+
+// "A", "A, B", "A, B, C", the same way artists are joined
+pub fn join_names(names: &[&str]) -> String {
+	names.join(globals::ARTIST_SEPARATOR)
+}
+
+#[test]
+fn credits_read_naturally() {
+	let credits = Credit::list_from_json(
+		&serde_json::json!([
+			{"role": "producer", "name": "Astro"},
+			{"role": "featured", "name": "Friend"},
+			{"role": "producer", "name": "Someone"},
+			{"role": "producer", "name": "Someone Else"}
+		]),
+		"Test"
+	);
+	assert_eq!(
+		describe(&credits),
+		vec![
+			"Produced by Astro, Someone, Someone Else",
+			"Featuring Friend"
+		]
+	);
+}
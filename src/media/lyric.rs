@@ -1,10 +1,17 @@
 use crate::build::smartquotes;
 use crate::build::xml::XmlNode;
+use crate::globals;
+use crate::media::vocalist::{Vocalist, VocalistRegistry};
+use crate::types::duration::Duration;
 use crate::types::language::Language;
+use crate::types::script::Script;
 
 const MAX_LINE_LENGTH: usize = 100;
 const MAX_LINE_LENGTH_FOR_SRT: usize = 65;
 const LYRIC_FILL_FORWARDS_MARGIN_SECONDS: f64 = 0.0; // i never know what's best
+const MAX_LYRICLESS_GAP_SECONDS: f64 = 60.0; // longer than this between lines gets a warning
+const MIN_LINE_SECONDS: f64 = 0.25; // shorter than this is probably a typo in the timestamps
+const ILLEGAL_CHARACTERS: &str = "–“”‘’()（）\r\t\n";
 
 #[derive(Debug)]
 pub enum TextCodec {
@@ -12,14 +19,25 @@ pub enum TextCodec {
 	Srt,
 	Lrc,
 	Vtt,
-	Tsv
+	Tsv,
+	Ttml,
+	Json
 }
-pub const ALL_TEXT_CODECS: [TextCodec; 5] = [
+pub const ALL_TEXT_CODECS: [TextCodec; 7] = [
 	TextCodec::Txt,
 	TextCodec::Srt,
 	TextCodec::Lrc,
 	TextCodec::Vtt,
-	TextCodec::Tsv
+	TextCodec::Tsv,
+	TextCodec::Ttml,
+	TextCodec::Json
+];
+// translations and romanizations are exported as extra files in these formats
+pub const LAYER_TEXT_CODECS: [TextCodec; 4] = [
+	TextCodec::Txt,
+	TextCodec::Srt,
+	TextCodec::Lrc,
+	TextCodec::Vtt
 ];
 impl TextCodec {
 	pub fn ext(&self) -> &'static str {
@@ -28,7 +46,9 @@ impl TextCodec {
 			TextCodec::Srt => "srt",
 			TextCodec::Lrc => "lrc",
 			TextCodec::Vtt => "vtt",
-			TextCodec::Tsv => "tsv"
+			TextCodec::Tsv => "tsv",
+			TextCodec::Ttml => "ttml",
+			TextCodec::Json => "json"
 		}
 	}
 	pub fn description(&self) -> &'static str {
@@ -37,9 +57,69 @@ impl TextCodec {
 			TextCodec::Srt => "SRT",
 			TextCodec::Lrc => "LRC",
 			TextCodec::Vtt => "VTT",
-			TextCodec::Tsv => "TSV"
+			TextCodec::Tsv => "TSV",
+			TextCodec::Ttml => "TTML",
+			TextCodec::Json => "JSON"
+		}
+	}
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LyricLayer {
+	Translation(Language),
+	Romanization
+}
+impl LyricLayer {
+	pub fn file_tag(&self) -> &'static str {
+		// lyrics.en.srt, lyrics.romanized.srt
+		match self {
+			LyricLayer::Translation(language) => language.iso_639_1(),
+			LyricLayer::Romanization => "romanized"
 		}
 	}
+	pub fn description(&self) -> String {
+		match self {
+			LyricLayer::Translation(language) => format!("{} translation", language.name()),
+			LyricLayer::Romanization => String::from("Romanization")
+		}
+	}
+}
+
+pub fn lyrics_location(maybe_parent_album_slug: Option<&str>, slug: &str) -> std::path::PathBuf {
+	let mut location = globals::filezone().join("source").join("lyrics");
+	if let Some(parent_album_slug) = maybe_parent_album_slug {
+		location = location.join(parent_album_slug);
+	}
+	location.join(slug).with_extension("tsv")
+}
+
+fn validate_layer_text(layer_text: &str, line_text: &str, text: &str) {
+	assert!(
+		!layer_text.is_empty()
+			&& !layer_text.starts_with(char::is_whitespace)
+			&& !layer_text.ends_with(char::is_whitespace),
+		"Translation or romanization \"{}\" of lyric line \"{}\" is empty or has untrimmed whitespace\n\n{}",
+		layer_text,
+		line_text,
+		text
+	);
+	assert!(
+		layer_text.chars().all(|c| !ILLEGAL_CHARACTERS.contains(c)),
+		"Translation or romanization \"{}\" of lyric line \"{}\" contains illegal character ({})\n\n{}",
+		layer_text,
+		line_text,
+		ILLEGAL_CHARACTERS,
+		text
+	);
+	assert!(
+		layer_text.chars().count() <= MAX_LINE_LENGTH,
+		"Translation or romanization \"{}\" of lyric line \"{}\" is too long ({} chars > {})\n\n{}",
+		layer_text,
+		line_text,
+		layer_text.chars().count(),
+		MAX_LINE_LENGTH,
+		text
+	);
 }
 
 fn parse_time(raw_text: &str, line: &str, text: &str) -> f64 {
@@ -71,7 +151,9 @@ fn parse_time(raw_text: &str, line: &str, text: &str) -> f64 {
 
 #[derive(Debug)]
 pub struct Lyrics {
-	stanzas: Vec<Vec<LyricLine>>
+	stanzas: Vec<Vec<LyricLine>>,
+	credits: Vec<Vocalist>, // everyone who sings, in order of appearance
+	duration: Duration      // of the song these lyrics belong to
 }
 impl Lyrics {
 	pub fn all_vocalist_sets(&self) -> Vec<&VocalistSet> {
@@ -86,7 +168,10 @@ impl Lyrics {
 		}
 		vocalist_sets
 	}
-	pub fn from(text: &str) -> Lyrics {
+	pub fn credits(&self) -> &[Vocalist] {
+		&self.credits
+	}
+	pub fn from(text: &str, vocalists: &VocalistRegistry, duration: Duration) -> Lyrics {
 		assert!(
 			!text.starts_with(char::is_whitespace),
 			"Lyric text has untrimmed whitespace at start\n\n{}",
@@ -121,7 +206,14 @@ impl Lyrics {
 							.map(|raw| parse_time(raw, line, text))
 							.collect::<Vec<_>>();
 						let (start, end) = (start_and_end[0], start_and_end[1]);
-						let the_text = parts[2].to_string();
+						// on lines with a sync: tag, "|" splits the text into segments (words or syllables)
+						let has_sync = parts[3..].iter().any(|kv| kv.starts_with("sync:"));
+						let segment_texts: Vec<&str> = if has_sync {
+							parts[2].split('|').collect()
+						} else {
+							vec![parts[2]]
+						};
+						let the_text = segment_texts.concat();
 						assert!(
 							!the_text.starts_with(char::is_whitespace),
 							"Lyric line \"{}\" ({}-{}) must not have whitespace at start\n\n{}",
@@ -138,12 +230,11 @@ impl Lyrics {
 							end,
 							text
 						);
-						let illegal_characters = "–“”‘’()（）\r\t\n";
 						assert!(
-							the_text.chars().all(|c| !illegal_characters.contains(c)),
+							the_text.chars().all(|c| !ILLEGAL_CHARACTERS.contains(c)),
 							"Lyric line \"{}\" contains illegal character ({})\n\n{}",
 							the_text,
-							illegal_characters,
+							ILLEGAL_CHARACTERS,
 							text
 						);
 						assert!(
@@ -174,6 +265,9 @@ impl Lyrics {
 						let mut vocalist_set_override: Option<VocalistSet> = None;
 
 						let mut vocalists_collected_this_line: Vec<String> = Vec::new();
+						let mut sync_times: Option<Vec<f64>> = None;
+						let mut translations: Vec<(Language, String)> = Vec::new();
+						let mut romanization: Option<String> = None;
 						for kv in &parts[3..] {
 							if let Some((key, value)) = kv.split_once(':') {
 								match key {
@@ -203,14 +297,61 @@ impl Lyrics {
 											new_vocalist,
 											text
 										);
-										/* if new_vocalist.to_lowercase() == "unknown" {
-											crate::globals::log_2(
-												"Warning",
-												format!("Unknown vocalist for line \"{}\"", the_text),
-												crate::globals::ANSI_RED
-											);
-										} */
-										vocalists_collected_this_line.push(new_vocalist);
+										let vocalist =
+											vocalists.resolve(&new_vocalist).unwrap_or_else(|| {
+												panic!(
+													"Vocalist \"{}\" (line \"{}\") isn't listed in discog.json \"vocalists\"\n\n{}",
+													new_vocalist, the_text, text
+												)
+											});
+										vocalists_collected_this_line.push(vocalist.name.clone());
+									}
+									"sync" => {
+										assert!(
+											sync_times.is_none(),
+											"Lyric line \"{}\" has more than one sync tag\n\n{}",
+											the_text,
+											text
+										);
+										sync_times = Some(
+											value
+												.split(',')
+												.map(|raw| parse_time(raw, line, text))
+												.collect()
+										);
+									}
+									"translation" => {
+										let (code, translated) =
+											value.split_once(':').unwrap_or_else(|| {
+												panic!(
+													"Lyric translation \"{}\" must look like translation:<language>:<text>\n\n{}",
+													value, text
+												)
+											});
+										let translation_language = Language::from(code);
+										assert!(
+											!translations
+												.iter()
+												.any(|(language, _)| *language
+													== translation_language),
+											"Lyric line \"{}\" has more than one translation:{} tag\n\n{}",
+											the_text,
+											code,
+											text
+										);
+										validate_layer_text(translated, &the_text, text);
+										translations
+											.push((translation_language, translated.to_string()));
+									}
+									"romanization" => {
+										assert!(
+											romanization.is_none(),
+											"Lyric line \"{}\" has more than one romanization tag\n\n{}",
+											the_text,
+											text
+										);
+										validate_layer_text(value, &the_text, text);
+										romanization = Some(value.to_string());
 									}
 									_ => panic!("Invalid lyric tag \"{}\"\n\n{}", key, text)
 								}
@@ -235,6 +376,15 @@ impl Lyrics {
 						} else {
 							panic!("First lyric line (\"{}\") has no language tag", line);
 						};
+						assert!(
+							!translations
+								.iter()
+								.any(|(translation_language, _)| *translation_language == language),
+							"Lyric line \"{}\" is translated into its own language {}\n\n{}",
+							the_text,
+							language.iso_639_1(),
+							text
+						);
 						let vocalist_set = if let Some(v) = vocalist_set_override {
 							last_vocalist_set = Some(v.clone());
 							v
@@ -273,12 +423,56 @@ impl Lyrics {
 								);
 							}
 						}
+						let words = match sync_times {
+							None => None,
+							Some(times) => {
+								assert!(
+									times.len() == segment_texts.len(),
+									"Lyric line \"{}\" has {} segments but {} sync timestamps\n\n{}",
+									the_text,
+									segment_texts.len(),
+									times.len(),
+									text
+								);
+								let mut words = Vec::new();
+								for (index, segment_text) in segment_texts.iter().enumerate() {
+									assert!(
+										!segment_text.trim().is_empty(),
+										"Lyric line \"{}\" has an empty sync segment\n\n{}",
+										the_text,
+										text
+									);
+									let word_start = times[index];
+									let word_end = times.get(index + 1).copied().unwrap_or(end);
+									assert!(
+										start <= word_start && word_start < word_end,
+										"Invalid sync timing for \"{}\" in lyric line \"{}\" ({} to {}, line {} to {})\n\n{}",
+										segment_text,
+										the_text,
+										word_start,
+										word_end,
+										start,
+										end,
+										text
+									);
+									words.push(LyricWord {
+										start: word_start,
+										end: word_end,
+										text: segment_text.to_string()
+									});
+								}
+								Some(words)
+							}
+						};
 						Some(LyricLine {
 							start,
 							end,
 							text: the_text,
 							language,
-							vocalist_set
+							vocalist_set,
+							words,
+							translations,
+							romanization
 						})
 					})
 					.collect()
@@ -319,10 +513,106 @@ impl Lyrics {
 				prev = Some((line.end, &line.text));
 			}
 		}
+		let song_seconds = f64::from(duration.milliseconds()) / 1000.0;
+		if let Some(last_line) = vvll.iter().flatten().last() {
+			assert!(
+				last_line.end <= song_seconds,
+				"Lyric line \"{}\" ends at {:.3}s, but the song is only {:.3}s long",
+				last_line.text,
+				last_line.end,
+				song_seconds
+			);
+		}
 
-		Lyrics { stanzas: vvll }
+		let mut credits: Vec<Vocalist> = Vec::new();
+		for line in vvll.iter().flatten() {
+			for name in line.vocalist_set.list.iter() {
+				if !credits.iter().any(|vocalist| vocalist.name == *name)
+					&& let Some(vocalist) = vocalists.resolve(name)
+				{
+					credits.push(vocalist.clone());
+				}
+			}
+		}
+
+		Lyrics {
+			stanzas: vvll,
+			credits,
+			duration
+		}
+	}
+	// fraction of the song that has a lyric line on screen. lines never overlap
+	pub fn coverage(&self) -> f64 {
+		let lyric_seconds: f64 = self
+			.stanzas
+			.iter()
+			.flatten()
+			.map(|line| line.end - line.start)
+			.sum();
+		lyric_seconds * 1000.0 / f64::from(self.duration.milliseconds())
 	}
-	pub fn as_filetype(&self, codec: TextCodec) -> String {
+	// things that are allowed but probably mistakes
+	pub fn warnings(&self) -> Vec<String> {
+		let mut warnings = Vec::new();
+		let lines: Vec<&LyricLine> = self.stanzas.iter().flatten().collect();
+		for line in &lines {
+			warnings.extend(script_warning(&line.text, line.language));
+			for (language, translated) in &line.translations {
+				warnings.extend(script_warning(translated, *language));
+			}
+			if let Some(romanized) = &line.romanization
+				&& Script::all_in(romanized)
+					.iter()
+					.any(|script| *script != Script::Latin)
+			{
+				warnings.push(format!(
+					"Romanization \"{}\" has non-Latin letters",
+					romanized
+				));
+			}
+			if line.end - line.start < MIN_LINE_SECONDS {
+				warnings.push(format!(
+					"Lyric line \"{}\" is only on screen for {:.3}s",
+					line.text,
+					line.end - line.start
+				));
+			}
+		}
+		for pair in lines.windows(2) {
+			if pair[1].start - pair[0].end > MAX_LYRICLESS_GAP_SECONDS {
+				warnings.push(format!(
+					"No lyrics for {:.0}s between \"{}\" and \"{}\"",
+					pair[1].start - pair[0].end,
+					pair[0].text,
+					pair[1].text
+				));
+			}
+		}
+		warnings
+	}
+	// every word from the list that shows up in the lyrics. list entries ending in * match prefixes
+	pub fn explicit_words(&self, word_list: &[String]) -> Vec<String> {
+		let mut found: Vec<String> = Vec::new();
+		for line in self.stanzas.iter().flatten() {
+			let lowercase = line.text.to_lowercase();
+			for word in lowercase
+				.split(|c: char| !c.is_alphanumeric() && c != '\'')
+				.map(|word| word.trim_matches('\''))
+			{
+				let matches = word_list
+					.iter()
+					.any(|listed| match listed.strip_suffix('*') {
+						Some(prefix) => word.starts_with(prefix),
+						None => word == listed
+					});
+				if matches && !found.iter().any(|already| already == word) {
+					found.push(word.to_string());
+				}
+			}
+		}
+		found
+	}
+	pub fn as_filetype(&self, codec: TextCodec, context: &LyricContext) -> String {
 		match codec {
 			TextCodec::Txt => self
 				.stanzas
@@ -336,16 +626,35 @@ impl Lyrics {
 				})
 				.collect::<Vec<_>>()
 				.join("\n\n"),
-			TextCodec::Lrc => self
-				.stanzas
-				.iter()
-				.map(|stanza_lines| {
-					let stanza_lines_text: Vec<String> =
-						stanza_lines.iter().map(LyricLine::to_synced_text).collect();
-					stanza_lines_text.join("\n")
-				})
-				.collect::<Vec<_>>()
-				.join("\n\n"),
+			TextCodec::Lrc => {
+				let mut headers = vec![
+					format!("[ar:{}]", context.artist),
+					format!("[ti:{}]", context.title),
+					format!("[al:{}]", context.album),
+				];
+				headers.push(format!(
+					"[length:{:02}:{:02}]",
+					context.duration.seconds() / 60,
+					context.duration.seconds() % 60
+				));
+				headers.push(format!("[by:{}]", globals::LYRICS_AUTHOR));
+				headers.push(String::from("[re:distri]"));
+				headers.push(format!("[ve:{}]", env!("CARGO_PKG_VERSION")));
+				let mut speakers = self.speakers().into_iter();
+				let body = self
+					.stanzas
+					.iter()
+					.map(|stanza_lines| {
+						let stanza_lines_text: Vec<String> = stanza_lines
+							.iter()
+							.map(|line| line.to_synced_text(speakers.next().flatten()))
+							.collect();
+						stanza_lines_text.join("\n")
+					})
+					.collect::<Vec<_>>()
+					.join("\n\n");
+				format!("{}\n\n{}", headers.join("\n"), body)
+			}
 			TextCodec::Srt => {
 				fn format_time(time: f64) -> String {
 					let ms = (time * 1000.0).round() as u64;
@@ -390,10 +699,9 @@ impl Lyrics {
 					format!("{}\n{}", left.trim_end(), right.trim_start())
 				}
 
+				let speakers = self.speakers();
 				let mut out = Vec::new();
-				let mut counter = 1;
-				for index in 0..lines.len() {
-					let line = lines[index];
+				for (index, line) in lines.iter().enumerate() {
 					let mut end = line.end;
 					if let Some(next) = lines.get(index + 1)
 						&& next.start > line.start
@@ -404,12 +712,11 @@ impl Lyrics {
 					}
 					out.push(format!(
 						"{}\n{} --> {}\n{}\n",
-						counter,
+						index + 1,
 						format_time(line.start),
 						format_time(end),
-						split_line_if_needed(&line.text)
+						split_line_if_needed(&with_speaker(&line.text, speakers[index]))
 					));
-					counter += 1;
 				}
 				out.join("\n")
 			}
@@ -438,13 +745,26 @@ impl Lyrics {
 					{
 						end = next.start;
 					}
+					let cue_text = match &line.words {
+						None => line.text.clone(),
+						Some(words) => words
+							.iter()
+							.map(|word| {
+								if word.start > line.start {
+									format!("<{}>{}", format_time(word.start), word.text)
+								} else {
+									word.text.clone()
+								}
+							})
+							.collect()
+					};
 					out.push(format!(
 						"{} --> {}\n<v {}>{}",
 						// counter,
 						format_time(line.start),
 						format_time(end),
 						line.vocalist_set,
-						line.text
+						cue_text
 					));
 					// counter += 1;
 				}
@@ -470,13 +790,310 @@ impl Lyrics {
 				}
 				out.join("\n").trim().to_string()
 			}
+			TextCodec::Ttml => format!(
+				"<?xml version=\"1.0\" encoding=\"UTF-8\"?>{}",
+				self.ttml_xml()
+			),
+			TextCodec::Json => self.json_value().to_string()
+		}
+	}
+	// for our own players and anyone else who doesn't want to scrape the lyric page
+	fn json_value(&self) -> serde_json::Value {
+		let mut vocalists: Vec<&String> = Vec::new();
+		for vocalist_set in self.all_vocalist_sets() {
+			for vocalist in vocalist_set.list.iter() {
+				if !vocalists.contains(&vocalist) {
+					vocalists.push(vocalist);
+				}
+			}
+		}
+		let stanzas: Vec<serde_json::Value> = self
+			.stanzas
+			.iter()
+			.map(|stanza| {
+				stanza
+					.iter()
+					.map(|line| {
+						let mut line_json = serde_json::json!({
+							"start": line.start,
+							"end": line.end,
+							"text": line.text,
+							"language": line.language.iso_639_1(),
+							"vocalists": *line.vocalist_set.list
+						});
+						if let Some(words) = &line.words {
+							line_json["words"] = words
+								.iter()
+								.enumerate()
+								.map(|(index, word)| {
+									serde_json::json!({
+										"start": word.start,
+										"end": words.get(index + 1).map_or(line.end, |next| next.start),
+										"text": word.text
+									})
+								})
+								.collect();
+						}
+						if !line.translations.is_empty() {
+							line_json["translations"] = line
+								.translations
+								.iter()
+								.map(|(language, translated)| {
+									(language.iso_639_1().to_string(), translated.clone().into())
+								})
+								.collect::<serde_json::Map<String, serde_json::Value>>()
+								.into();
+						}
+						if let Some(romanized) = &line.romanization {
+							line_json["romanization"] = romanized.clone().into();
+						}
+						line_json
+					})
+					.collect()
+			})
+			.collect();
+		serde_json::json!({
+			"language": self.most_common_language().iso_639_1(),
+			"vocalists": vocalists,
+			"stanzas": stanzas
+		})
+	}
+	// apple-flavored ttml: itunes:key on every line so translations can point at them
+	fn ttml_xml(&self) -> XmlNode {
+		let all_vocalist_sets = self.all_vocalist_sets();
+		let agent_id = |vocalist_set: &VocalistSet| {
+			let index = all_vocalist_sets
+				.iter()
+				.position(|other| *other == vocalist_set)
+				.unwrap_or_else(|| {
+					panic!(
+						"Vocalist set {} is missing from its own lyrics",
+						vocalist_set
+					)
+				});
+			format!("v{}", index + 1)
+		};
+		let has_word_timing = self
+			.stanzas
+			.iter()
+			.flatten()
+			.any(|line| line.words.is_some());
+
+		let mut metadata = XmlNode::new("metadata");
+		for vocalist_set in &all_vocalist_sets {
+			metadata.add_child(
+				XmlNode::new("ttm:agent")
+					.with_attribute(
+						"type",
+						if vocalist_set.list.len() > 1 {
+							"group"
+						} else {
+							"person"
+						}
+					)
+					.with_attribute("xml:id", agent_id(vocalist_set))
+					.with_child(
+						XmlNode::new("ttm:name")
+							.with_attribute("type", "full")
+							.with_text(vocalist_set.to_string())
+					)
+			);
+		}
+		let layers = self.layers();
+		let has_romanization = layers.contains(&LyricLayer::Romanization);
+		let has_translations = layers.len() > usize::from(has_romanization);
+		if !layers.is_empty() {
+			let mut translations = XmlNode::new("translations");
+			let mut transliterations = XmlNode::new("transliterations");
+			for layer in layers {
+				let mut texts: Vec<XmlNode> = Vec::new();
+				for (key_number, line) in (1..).zip(self.stanzas.iter().flatten()) {
+					let maybe_layer_text = match layer {
+						LyricLayer::Translation(language) => line
+							.translations
+							.iter()
+							.find(|(translation_language, _)| *translation_language == language)
+							.map(|(_, translated)| translated),
+						LyricLayer::Romanization => line.romanization.as_ref()
+					};
+					if let Some(layer_text) = maybe_layer_text {
+						texts.push(
+							XmlNode::new("text")
+								.with_attribute("for", format!("L{}", key_number))
+								.with_text(layer_text)
+						);
+					}
+				}
+				match layer {
+					LyricLayer::Translation(language) => {
+						let mut translation = XmlNode::new("translation")
+							.with_attribute("type", "subtitle")
+							.with_attribute("xml:lang", language.iso_639_1());
+						for text in texts {
+							translation.add_child(text);
+						}
+						translations.add_child(translation);
+					}
+					LyricLayer::Romanization => {
+						let mut transliteration = XmlNode::new("transliteration").with_attribute(
+							"xml:lang",
+							format!("{}-Latn", self.most_common_language().iso_639_1())
+						);
+						for text in texts {
+							transliteration.add_child(text);
+						}
+						transliterations.add_child(transliteration);
+					}
+				}
+			}
+			metadata.add_child(
+				XmlNode::new("iTunesMetadata")
+					.with_attribute("xmlns", "http://music.apple.com/lyric-ttml-internal")
+					.maybe_with_child(has_translations.then_some(translations))
+					.maybe_with_child(has_romanization.then_some(transliterations))
+			);
+		}
+
+		let mut body = XmlNode::new("body");
+		let mut key_numbers = 1..;
+		for stanza in &self.stanzas {
+			let (Some(first_line), Some(last_line)) = (stanza.first(), stanza.last()) else {
+				continue;
+			};
+			let mut div = XmlNode::new("div")
+				.with_attribute("begin", ttml_timestamp(first_line.start))
+				.with_attribute("end", ttml_timestamp(last_line.end));
+			for line in stanza {
+				let key_number = key_numbers.next().unwrap_or_default();
+				let mut paragraph = XmlNode::new("p")
+					.with_attribute("begin", ttml_timestamp(line.start))
+					.with_attribute("end", ttml_timestamp(line.end))
+					.with_attribute("itunes:key", format!("L{}", key_number))
+					.with_attribute("ttm:agent", agent_id(&line.vocalist_set))
+					.with_attribute("xml:lang", line.language.iso_639_1());
+				match &line.words {
+					None => paragraph.add_text(&line.text),
+					Some(words) => {
+						for (index, word) in words.iter().enumerate() {
+							let word_end = words.get(index + 1).map_or(line.end, |next| next.start);
+							paragraph.add_child(
+								XmlNode::new("span")
+									.with_attribute("begin", ttml_timestamp(word.start))
+									.with_attribute("end", ttml_timestamp(word_end))
+									.with_text(word.text.trim())
+							);
+							if word.text.ends_with(char::is_whitespace) {
+								paragraph.add_text(" ");
+							}
+						}
+					}
+				}
+				div.add_child(paragraph);
+			}
+			body.add_child(div);
+		}
+		if let Some(last_line) = self.stanzas.iter().flatten().last() {
+			body.add_attribute("dur", ttml_timestamp(last_line.end));
 		}
+
+		XmlNode::new("tt")
+			.with_attribute("xmlns", "http://www.w3.org/ns/ttml")
+			.with_attribute("xmlns:ttm", "http://www.w3.org/ns/ttml#metadata")
+			.with_attribute("xmlns:itunes", "http://music.apple.com/lyric-ttml-internal")
+			.with_attribute(
+				"itunes:timing",
+				if has_word_timing { "Word" } else { "Line" }
+			)
+			.with_attribute("xml:lang", self.most_common_language().iso_639_1())
+			.with_child(XmlNode::new("head").with_child(metadata))
+			.with_child(body)
+	}
+	pub fn layers(&self) -> Vec<LyricLayer> {
+		let mut layers: Vec<LyricLayer> = Vec::new();
+		for line in self.stanzas.iter().flatten() {
+			for (language, _) in &line.translations {
+				if !layers.contains(&LyricLayer::Translation(*language)) {
+					layers.push(LyricLayer::Translation(*language));
+				}
+			}
+		}
+		if self
+			.stanzas
+			.iter()
+			.flatten()
+			.any(|line| line.romanization.is_some())
+		{
+			layers.push(LyricLayer::Romanization);
+		}
+		layers
+	}
+	// the same lyrics, but with a translation or romanization swapped in wherever a line has one
+	pub fn with_layer(&self, layer: LyricLayer) -> Lyrics {
+		Lyrics {
+			stanzas: self
+				.stanzas
+				.iter()
+				.map(|stanza| {
+					stanza
+						.iter()
+						.map(|line| {
+							let (text, language) = match layer {
+								LyricLayer::Translation(target) => {
+									match line
+										.translations
+										.iter()
+										.find(|(language, _)| *language == target)
+									{
+										Some((_, translated)) => (translated.clone(), target),
+										None => (line.text.clone(), line.language)
+									}
+								}
+								LyricLayer::Romanization => (
+									line.romanization
+										.clone()
+										.unwrap_or_else(|| line.text.clone()),
+									line.language
+								)
+							};
+							LyricLine {
+								start: line.start,
+								end: line.end,
+								text,
+								language,
+								vocalist_set: line.vocalist_set.clone(),
+								words: None,
+								translations: Vec::new(),
+								romanization: None
+							}
+						})
+						.collect()
+				})
+				.collect(),
+			credits: self.credits.clone(),
+			duration: self.duration
+		}
+	}
+	// who's singing, for every line where that changes. only when there's more than one vocalist set
+	fn speakers(&self) -> Vec<Option<&VocalistSet>> {
+		let mut speakers = Vec::new();
+		let mut last_vocalist_set: Option<&VocalistSet> = None;
+		let label_speakers = self.all_vocalist_sets().len() > 1;
+		for line in self.stanzas.iter().flatten() {
+			if label_speakers && last_vocalist_set != Some(&line.vocalist_set) {
+				speakers.push(Some(&line.vocalist_set));
+			} else {
+				speakers.push(None);
+			}
+			last_vocalist_set = Some(&line.vocalist_set);
+		}
+		speakers
 	}
 	pub fn as_sylt_data(&self) -> Vec<(u32, String)> {
 		let mut synced_content: Vec<(u32, String)> = Vec::new();
+		let mut speakers = self.speakers().into_iter();
 		for stanza_lines in &self.stanzas {
 			for line in stanza_lines {
-				synced_content.push(line.to_synced_pair());
+				synced_content.push(line.to_synced_pair(speakers.next().flatten()));
 			}
 			if let Some(last_line) = stanza_lines.last() {
 				synced_content.push((last_line.end_ms(), String::new()));
@@ -527,19 +1144,52 @@ impl Lyrics {
 						);
 					}
 				}
+				let mut l_l = XmlNode::new("l-l");
+				match &line.words {
+					None => l_l.add_text(smartquotes::smart_quotes(&line.text)),
+					Some(words) => {
+						// smart_quotes maps characters one-to-one, so quote the whole line for context and then cut it back up
+						let mut quoted_chars = smartquotes::smart_quotes(&line.text)
+							.chars()
+							.collect::<Vec<_>>()
+							.into_iter();
+						for word in words {
+							let quoted_word: String = quoted_chars
+								.by_ref()
+								.take(word.text.chars().count())
+								.collect();
+							l_l.add_child(
+								XmlNode::new("l-w")
+									.with_text(quoted_word)
+									.with_attribute("data-start", format!("{:.6}", word.start))
+									.with_attribute("data-end", format!("{:.6}", word.end))
+							);
+						}
+					}
+				}
+				for (language, translated) in &line.translations {
+					l_l.add_child(
+						XmlNode::new("l-t")
+							.with_attribute("lang", language.iso_639_1())
+							.with_text(smartquotes::smart_quotes(translated))
+					);
+				}
+				if let Some(romanized) = &line.romanization {
+					l_l.add_child(
+						XmlNode::new("l-r").with_text(smartquotes::smart_quotes(romanized))
+					);
+				}
 				l_s.add_child(
-					XmlNode::new("l-l")
-						.with_text(smartquotes::smart_quotes(&line.text))
-						.maybe_with_attribute(
-							"lang",
-							if line.language == primary_language {
-								None
-							} else {
-								Some(line.language.iso_639_1())
-							}
-						)
-						.with_attribute("data-start", format!("{:.6}", line.start))
-						.with_attribute("data-end", format!("{:.6}", line.end))
+					l_l.maybe_with_attribute(
+						"lang",
+						if line.language == primary_language {
+							None
+						} else {
+							Some(line.language.iso_639_1())
+						}
+					)
+					.with_attribute("data-start", format!("{:.6}", line.start))
+					.with_attribute("data-end", format!("{:.6}", line.end))
 				)
 			}
 			l_a.add_child(l_s);
@@ -555,7 +1205,17 @@ struct LyricLine {
 	end: f64,
 	text: String,
 	language: Language,
-	vocalist_set: VocalistSet
+	vocalist_set: VocalistSet,
+	words: Option<Vec<LyricWord>>, // karaoke timing; texts concatenate to the line's text
+	translations: Vec<(Language, String)>,
+	romanization: Option<String>
+}
+
+#[derive(Debug)]
+struct LyricWord {
+	start: f64,
+	end: f64,
+	text: String
 }
 impl LyricLine {
 	fn start_ms(&self) -> u32 {
@@ -567,21 +1227,90 @@ impl LyricLine {
 	fn to_unsynced(&self) -> String {
 		self.text.clone()
 	}
-	fn to_synced_pair(&self) -> (u32, String) {
-		(self.start_ms(), self.text.clone())
+	fn to_synced_pair(&self, speaker: Option<&VocalistSet>) -> (u32, String) {
+		(self.start_ms(), with_speaker(&self.text, speaker))
 	}
-	fn to_synced_text(&self) -> String {
-		let total_ms = self.start_ms();
-		let minutes = total_ms / 60000;
-		let seconds = (total_ms % 60000) / 1000;
-		let hundredths = (total_ms % 1000) / 10; // two‑digit fraction
-		format!(
-			"[{:02}:{:02}.{:02}] {}",
-			minutes, seconds, hundredths, self.text
-		)
+	fn to_synced_text(&self, speaker: Option<&VocalistSet>) -> String {
+		match &self.words {
+			None => format!(
+				"[{}] {}",
+				lrc_timestamp(self.start),
+				with_speaker(&self.text, speaker)
+			),
+			// enhanced lrc: <mm:ss.xx> before every word, and once more at the end
+			Some(words) => format!(
+				"[{}] {}{}<{}>",
+				lrc_timestamp(self.start),
+				with_speaker("", speaker),
+				words
+					.iter()
+					.map(|word| format!("<{}>{}", lrc_timestamp(word.start), word.text))
+					.collect::<String>(),
+				lrc_timestamp(self.end)
+			)
+		}
 	}
 }
 
+// catches language: tags that were inherited when they shouldn't have been (kana tagged en, english tagged ja)
+fn script_warning(text: &str, language: Language) -> Option<String> {
+	let found = Script::all_in(text);
+	let expected = language.scripts();
+	let has_unexpected_script = found
+		.iter()
+		.any(|script| *script != Script::Latin && !expected.contains(script));
+	let has_expected_script = found.iter().any(|script| expected.contains(script));
+	if has_unexpected_script || (!found.is_empty() && !has_expected_script) {
+		Some(format!(
+			"\"{}\" is tagged {} ({}) but is written in {}",
+			text,
+			language.name(),
+			language.iso_639_1(),
+			found
+				.iter()
+				.map(|script| format!("{:?}", script))
+				.collect::<Vec<_>>()
+				.join(" + ")
+		))
+	} else {
+		None
+	}
+}
+
+fn with_speaker(text: &str, speaker: Option<&VocalistSet>) -> String {
+	match speaker {
+		Some(vocalist_set) => format!("{}: {}", vocalist_set, text),
+		None => text.to_string()
+	}
+}
+
+// what lyric files get to know about the song they're for
+pub struct LyricContext {
+	pub artist: String,
+	pub title: String,
+	pub album: String, // loose singles are their own album, like in the tags
+	pub duration: Duration
+}
+
+fn lrc_timestamp(time: f64) -> String {
+	let total_ms = (time * 1000.0).round() as u32;
+	let minutes = total_ms / 60000;
+	let seconds = (total_ms % 60000) / 1000;
+	let hundredths = (total_ms % 1000) / 10; // two‑digit fraction
+	format!("{:02}:{:02}.{:02}", minutes, seconds, hundredths)
+}
+
+fn ttml_timestamp(time: f64) -> String {
+	let total_ms = (time * 1000.0).round() as u64;
+	format!(
+		"{:02}:{:02}:{:02}.{:03}",
+		total_ms / 3_600_000,
+		(total_ms % 3_600_000) / 60_000,
+		(total_ms % 60_000) / 1000,
+		total_ms % 1000
+	)
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct VocalistSet {
 	list: std::rc::Rc<Vec<String>>
@@ -609,3 +1338,126 @@ impl std::fmt::Display for VocalistSet {
 		std::rc::Rc::ptr_eq(&self.list, &other.list)
 	}
 } */
+
+#[test]
+fn word_timing_becomes_enhanced_lrc() {
+	let vocalists = VocalistRegistry::from_json(&serde_json::json!([{"name": "Astro"}]));
+	let lyrics = Lyrics::from(
+		"12.000000\t14.500000\tHel|lo |world\tlanguage:en\tvocalist:Astro\tsync:12.000000,12.400000,12.900000",
+		&vocalists,
+		Duration::from_milliseconds(15_000)
+	);
+	let context = LyricContext {
+		artist: String::from("Astro"),
+		title: String::from("Hello"),
+		album: String::from("Hello"),
+		duration: Duration::from_milliseconds(15_000)
+	};
+	assert!(
+		lyrics
+			.as_filetype(TextCodec::Lrc, &context)
+			.ends_with("\n\n[00:12.00] <00:12.00>Hel<00:12.40>lo <00:12.90>world<00:14.50>")
+	);
+	assert_eq!(lyrics.as_filetype(TextCodec::Txt, &context), "Hello world");
+	assert!((lyrics.coverage() - 2.5 / 15.0).abs() < 1e-9);
+	assert_eq!(
+		lyrics.json_value()["stanzas"][0][0]["words"][1],
+		serde_json::json!({"start": 12.4, "end": 12.9, "text": "lo "})
+	);
+	assert!(
+		lyrics
+			.as_filetype(TextCodec::Ttml, &context)
+			.contains("<span begin=\"00:00:12.400\" end=\"00:00:12.900\">lo</span> <span")
+	);
+	assert!(
+		lyrics
+			.lyric_page_xml()
+			.to_string()
+			.contains("<l-w data-start=\"12.400000\" data-end=\"12.900000\">lo </l-w>")
+	);
+	let unsynced = Lyrics::from(
+		"12.000000\t14.500000\tThis|that\tlanguage:en\tvocalist:Astro",
+		&vocalists,
+		Duration::from_milliseconds(15_000)
+	);
+	assert_eq!(unsynced.as_filetype(TextCodec::Txt, &context), "This|that");
+}
+
+#[test]
+fn translation_layer_falls_back_to_original() {
+	let vocalists = VocalistRegistry::from_json(&serde_json::json!([
+		{"name": "Astro"},
+		{"name": "Friend", "aliases": ["Fren"]}
+	]));
+	let lyrics = Lyrics::from(
+		"1.000000\t2.000000\tこんにちは\tlanguage:ja\tvocalist:Astro\ttranslation:en:Hello\tromanization:Konnichiwa\n2.000000\t3.000000\tOK\tlanguage:en\tvocalist:fren",
+		&vocalists,
+		Duration::from_milliseconds(4_000)
+	);
+	let context = LyricContext {
+		artist: String::from("Astro"),
+		title: String::from("Konnichiwa"),
+		album: String::from("Konnichiwa"),
+		duration: Duration::from_milliseconds(4_000)
+	};
+	assert_eq!(
+		lyrics.layers(),
+		vec![
+			LyricLayer::Translation(Language::English),
+			LyricLayer::Romanization
+		]
+	);
+	let translated = lyrics.with_layer(LyricLayer::Translation(Language::English));
+	assert_eq!(
+		translated.as_filetype(TextCodec::Txt, &context),
+		"Hello\nOK"
+	);
+	let romanized = lyrics.with_layer(LyricLayer::Romanization);
+	assert_eq!(
+		romanized.as_filetype(TextCodec::Txt, &context),
+		"Konnichiwa\nOK"
+	);
+	assert_eq!(
+		romanized.as_sylt_data(),
+		vec![
+			(1000, String::from("Astro: Konnichiwa")),
+			(2000, String::from("Friend: OK")),
+			(3000, String::new())
+		]
+	);
+}
+
+#[test]
+#[should_panic(expected = "but the song is only")]
+fn lyrics_cannot_outlast_the_song() {
+	let vocalists = VocalistRegistry::from_json(&serde_json::json!([{"name": "Astro"}]));
+	let _ = Lyrics::from(
+		"1.000000\t5.000000\tToo long\tlanguage:en\tvocalist:Astro",
+		&vocalists,
+		Duration::from_milliseconds(4_000)
+	);
+}
+
+#[test]
+fn mismatched_scripts_are_flagged() {
+	assert!(script_warning("こんにちは", Language::English).is_some());
+	assert!(script_warning("Hello again", Language::Japanese).is_some());
+	assert!(script_warning("Hello 世界", Language::Japanese).is_none());
+	assert!(script_warning("Привет", Language::Russian).is_none());
+	assert!(script_warning("123!", Language::Korean).is_none());
+}
+
+#[test]
+fn explicit_words_are_found() {
+	let vocalists = VocalistRegistry::from_json(&serde_json::json!([{"name": "Astro"}]));
+	let lyrics = Lyrics::from(
+		"1.000000\t2.000000\tWhat the Hell, darn it\tlanguage:en\tvocalist:Astro\n2.000000\t3.000000\tShellfish and darnation",
+		&vocalists,
+		Duration::from_milliseconds(4_000)
+	);
+	let word_list = vec![String::from("hell"), String::from("darn*")];
+	assert_eq!(
+		lyrics.explicit_words(&word_list),
+		vec!["hell", "darn", "darnation"]
+	);
+}
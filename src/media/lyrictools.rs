@@ -0,0 +1,703 @@
+// `distri lyrics ...`: helpers for getting lyrics into (and around) the tsv source format
+
+use crate::globals;
+use crate::media::{
+	lyric::{self, Lyrics},
+	vocalist::VocalistRegistry
+};
+use crate::types::duration::Duration;
+
+const IMPORT_STANZA_GAP_SECONDS: f64 = 3.0; // silence longer than this between lines starts a new stanza
+const IMPORT_LAST_LINE_SECONDS: f64 = 5.0; // lrc has no end time for the last line
+
+const USAGE: &str = "distri lyrics import <file.lrc|file.srt|file.vtt> --song <slug> [--language <code>] [--vocalist <name>] [--force]
+       distri lyrics retime --song <slug> [--shift <seconds>] [--stretch <factor>] [--pivot <seconds>] [--lines <from>-<to>]
+       distri lyrics retime --song <slug> --anchor <old>=<new> --anchor <old>=<new> [--lines <from>-<to>]";
+
+pub fn distri_lyrics(args: &[String]) {
+	match args.first().map(String::as_str) {
+		Some("import") => import(&args[1..]),
+		Some("retime") => retime(&args[1..]),
+		_ => panic!("Usage: {}", USAGE)
+	}
+}
+
+fn discog_json_location() -> std::path::PathBuf {
+	globals::filezone()
+		.join("source")
+		.join("discog")
+		.with_extension("json")
+}
+
+// what the lyric tools need to know about a song. found straight from discog.json, so the
+// song's own (maybe missing or broken) lyrics never get loaded
+struct SongSource {
+	artists: Vec<String>,
+	duration: Duration,
+	location: std::path::PathBuf, // where its lyric tsv lives (or would live)
+	has_lyrics: bool
+}
+
+fn find_song_source(slug: &str) -> (SongSource, VocalistRegistry) {
+	let discog = crate::media::read_discog_json(&discog_json_location());
+	let vocalists = discog
+		.get("vocalists")
+		.map_or_else(VocalistRegistry::default, VocalistRegistry::from_json);
+	let list = |key: &str| {
+		discog
+			.get(key)
+			.and_then(serde_json::Value::as_array)
+			.unwrap_or_else(|| panic!("discog.json \"{}\" attribute is not an array", key))
+	};
+	// the same slugs Album::from_json and Song::from_json come up with
+	let slug_of = |val: &serde_json::Value, what: &str| match val
+		.get("slug")
+		.and_then(serde_json::Value::as_str)
+	{
+		Some(custom_slug) => custom_slug.to_string(),
+		None => globals::compute_slug(
+			&globals::parse_artists(val.get("artist"), what),
+			val.get("title")
+				.and_then(serde_json::Value::as_str)
+				.unwrap_or_default()
+		)
+	};
+	let album_songs = list("albums").iter().flat_map(|album| {
+		let album_slug = slug_of(album, "Album");
+		album
+			.get("songs")
+			.and_then(serde_json::Value::as_array)
+			.into_iter()
+			.flatten()
+			.map(move |song| (Some(album_slug.clone()), song))
+	});
+	let (maybe_parent_album_slug, song) = album_songs
+		.chain(list("remixes").iter().map(|song| (None, song)))
+		.find(|(_, song)| slug_of(song, "Song") == slug)
+		.unwrap_or_else(|| panic!("No song has the slug \"{}\"", slug));
+	(
+		SongSource {
+			artists: globals::parse_artists(song.get("artist"), "Song"),
+			duration: Duration::from_audio_file_and_validate(
+				maybe_parent_album_slug.as_deref(),
+				slug
+			),
+			location: lyric::lyrics_location(maybe_parent_album_slug.as_deref(), slug),
+			has_lyrics: song
+				.get("lyrics")
+				.and_then(serde_json::Value::as_bool)
+				.unwrap_or(false)
+		},
+		vocalists
+	)
+}
+
+// a line as it came out of someone else's format, before it becomes a tsv row
+#[derive(Debug, PartialEq)]
+struct ImportedLine {
+	start: f64,
+	end: Option<f64>, // lrc only knows when the next line starts
+	text: String,
+	vocalists: Vec<String> // empty means "same as before"
+}
+
+fn import(args: &[String]) {
+	let mut maybe_file: Option<&str> = None;
+	let mut maybe_slug: Option<&str> = None;
+	let mut language = "en";
+	let mut maybe_vocalist: Option<&str> = None;
+	let mut force = false;
+	let mut remaining = args.iter();
+	while let Some(arg) = remaining.next() {
+		let mut value_for = |flag: &str| {
+			remaining
+				.next()
+				.unwrap_or_else(|| panic!("{} needs a value\nUsage: {}", flag, USAGE))
+				.as_str()
+		};
+		match arg.as_str() {
+			"--song" => maybe_slug = Some(value_for("--song")),
+			"--language" => language = value_for("--language"),
+			"--vocalist" => maybe_vocalist = Some(value_for("--vocalist")),
+			"--force" => force = true,
+			flag if flag.starts_with("--") => panic!("Unknown flag {}\nUsage: {}", flag, USAGE),
+			file => {
+				assert!(
+					maybe_file.is_none(),
+					"Only one file can be imported at a time"
+				);
+				maybe_file = Some(file);
+			}
+		}
+	}
+	let file = maybe_file.unwrap_or_else(|| panic!("No file to import\nUsage: {}", USAGE));
+	let slug = maybe_slug.unwrap_or_else(|| panic!("No --song given\nUsage: {}", USAGE));
+	let _ = crate::types::language::Language::from(language);
+
+	let (song, vocalists) = find_song_source(slug);
+	let destination = song.location;
+	assert!(
+		force || !destination.exists(),
+		"{} already exists; use --force to overwrite it",
+		destination.display()
+	);
+
+	let source = std::fs::read_to_string(file)
+		.unwrap_or_else(|_| panic!("Couldn't read lyrics file {}", file));
+	let extension = std::path::Path::new(file)
+		.extension()
+		.map(|extension| extension.to_string_lossy().to_lowercase());
+	let stanzas = match extension.as_deref() {
+		Some("lrc") => parse_lrc(&source, &vocalists),
+		Some("srt") => parse_srt(&source, &vocalists),
+		Some("vtt") => parse_vtt(&source),
+		_ => panic!("Can only import .lrc, .srt, or .vtt files, not {}", file)
+	};
+	let default_vocalist = match maybe_vocalist {
+		Some(name) => vocalists.resolve(name).unwrap_or_else(|| {
+			panic!(
+				"--vocalist \"{}\" isn't listed in discog.json \"vocalists\"",
+				name
+			)
+		}),
+		None => vocalists.resolve(&song.artists[0]).unwrap_or_else(|| {
+			panic!(
+				"\"{}\" isn't listed in discog.json \"vocalists\"; add them or pass --vocalist",
+				song.artists[0]
+			)
+		})
+	};
+	let song_seconds = f64::from(song.duration.milliseconds()) / 1000.0;
+	let tsv = to_tsv(&stanzas, language, &default_vocalist.name, song_seconds);
+
+	// same validation as everything else. panics here mean the source file needs fixing
+	let lyrics = Lyrics::from(&tsv, &vocalists, song.duration);
+	for warning in lyrics.warnings() {
+		globals::log_2("Warning", warning, globals::ANSI_RED);
+	}
+
+	if let Some(parent) = destination.parent() {
+		std::fs::create_dir_all(parent)
+			.unwrap_or_else(|_| panic!("Couldn't create directory {}", parent.display()));
+	}
+	std::fs::write(&destination, tsv)
+		.unwrap_or_else(|_| panic!("Couldn't write to file {}", destination.display()));
+	globals::log_3(
+		"Imported",
+		format!("{:.0}%", lyrics.coverage() * 100.0),
+		destination.display(),
+		globals::ANSI_GREEN
+	);
+}
+
+// new = (old - pivot) * factor + pivot + shift
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Retiming {
+	factor: f64,
+	pivot: f64,
+	shift: f64
+}
+
+impl Retiming {
+	// the line through two (old, new) points
+	fn from_anchors((old_a, new_a): (f64, f64), (old_b, new_b): (f64, f64)) -> Retiming {
+		assert!(
+			(old_b - old_a).abs() > f64::EPSILON,
+			"Anchors must be at two different times"
+		);
+		Retiming {
+			factor: (new_b - new_a) / (old_b - old_a),
+			pivot: old_a,
+			shift: new_a - old_a
+		}
+	}
+	fn apply(&self, seconds: f64) -> f64 {
+		(seconds - self.pivot) * self.factor + self.pivot + self.shift
+	}
+}
+
+fn parse_seconds(raw: &str, flag: &str) -> f64 {
+	parse_clock(raw).unwrap_or_else(|| panic!("{} value \"{}\" is not a time", flag, raw))
+}
+
+fn retime(args: &[String]) {
+	let mut maybe_slug: Option<&str> = None;
+	let mut shift = 0.0;
+	let mut factor = 1.0;
+	let mut pivot = 0.0;
+	let mut anchors: Vec<(f64, f64)> = Vec::new();
+	let mut maybe_lines: Option<(usize, usize)> = None;
+	let mut remaining = args.iter();
+	while let Some(arg) = remaining.next() {
+		let mut value_for = |flag: &str| {
+			remaining
+				.next()
+				.unwrap_or_else(|| panic!("{} needs a value\nUsage: {}", flag, USAGE))
+				.as_str()
+		};
+		match arg.as_str() {
+			"--song" => maybe_slug = Some(value_for("--song")),
+			// shifts can be negative, so no parse_clock here
+			"--shift" => {
+				let raw = value_for("--shift");
+				shift = raw
+					.parse()
+					.unwrap_or_else(|_| panic!("--shift value \"{}\" is not a number", raw));
+			}
+			"--stretch" => {
+				let raw = value_for("--stretch");
+				factor = raw
+					.parse()
+					.unwrap_or_else(|_| panic!("--stretch value \"{}\" is not a number", raw));
+				assert!(factor > 0.0, "--stretch factor must be positive");
+			}
+			"--pivot" => pivot = parse_seconds(value_for("--pivot"), "--pivot"),
+			"--anchor" => {
+				let raw = value_for("--anchor");
+				let (old, new) = raw
+					.split_once('=')
+					.unwrap_or_else(|| panic!("--anchor must look like <old>=<new>, not {}", raw));
+				anchors.push((
+					parse_seconds(old, "--anchor"),
+					parse_seconds(new, "--anchor")
+				));
+			}
+			"--lines" => {
+				let raw = value_for("--lines");
+				let (from, to) = raw
+					.split_once('-')
+					.and_then(|(from, to)| Some((from.parse().ok()?, to.parse().ok()?)))
+					.unwrap_or_else(|| panic!("--lines must look like <from>-<to>, not {}", raw));
+				assert!(
+					from >= 1 && from <= to,
+					"--lines range {} is backwards or starts before line 1",
+					raw
+				);
+				maybe_lines = Some((from, to));
+			}
+			flag => panic!("Unknown argument {}\nUsage: {}", flag, USAGE)
+		}
+	}
+	let slug = maybe_slug.unwrap_or_else(|| panic!("No --song given\nUsage: {}", USAGE));
+	let retiming = match anchors.as_slice() {
+		[] => Retiming {
+			factor,
+			pivot,
+			shift
+		},
+		[anchor_a, anchor_b] => {
+			assert!(
+				shift == 0.0 && factor == 1.0 && pivot == 0.0,
+				"--anchor can't be combined with --shift, --stretch, or --pivot"
+			);
+			Retiming::from_anchors(*anchor_a, *anchor_b)
+		}
+		_ => panic!("Give exactly two --anchor points")
+	};
+
+	// the current tsv isn't validated, since it might be the thing that needs retiming to pass
+	let (song, vocalists) = find_song_source(slug);
+	let location = song.location;
+	assert!(song.has_lyrics, "{} has no lyrics to retime", slug);
+	let text = std::fs::read_to_string(&location)
+		.unwrap_or_else(|_| panic!("Couldn't read lyrics file {}", location.display()));
+	let retimed = retime_tsv(&text, retiming, maybe_lines);
+
+	// panics here (lines ending after the song, etc) leave the original tsv alone
+	let lyrics = Lyrics::from(&retimed, &vocalists, song.duration);
+	for warning in lyrics.warnings() {
+		globals::log_2("Warning", warning, globals::ANSI_RED);
+	}
+	std::fs::write(&location, retimed)
+		.unwrap_or_else(|_| panic!("Couldn't write to file {}", location.display()));
+	globals::log_3(
+		"Retimed",
+		format!("{:.0}%", lyrics.coverage() * 100.0),
+		location.display(),
+		globals::ANSI_GREEN
+	);
+}
+
+// rewrites the start, end, and sync: times of every lyric line (or lines from..=to, counting from 1).
+// everything else in the tsv is left exactly as it was
+fn retime_tsv(text: &str, retiming: Retiming, maybe_lines: Option<(usize, usize)>) -> String {
+	let format_time = |raw: &str| {
+		let seconds = raw
+			.parse::<f64>()
+			.unwrap_or_else(|_| panic!("Invalid timestamp \"{}\"", raw));
+		let new_seconds = retiming.apply(seconds);
+		assert!(
+			new_seconds >= 0.0,
+			"Retiming moves {} to before the start of the song ({:.6})",
+			raw,
+			new_seconds
+		);
+		format!("{:.6}", new_seconds)
+	};
+	let mut line_number = 0;
+	let mut lines_out: Vec<String> = Vec::new();
+	for line in text.lines() {
+		if line.is_empty() {
+			lines_out.push(String::new());
+			continue;
+		}
+		line_number += 1;
+		if let Some((from, to)) = maybe_lines
+			&& !(from..=to).contains(&line_number)
+		{
+			lines_out.push(line.to_string());
+			continue;
+		}
+		let parts: Vec<String> = line
+			.split('\t')
+			.enumerate()
+			.map(|(index, part)| {
+				if index < 2 {
+					format_time(part)
+				} else if let Some(sync) = part.strip_prefix("sync:") {
+					format!(
+						"sync:{}",
+						sync.split(',')
+							.map(format_time)
+							.collect::<Vec<_>>()
+							.join(",")
+					)
+				} else {
+					part.to_string()
+				}
+			})
+			.collect();
+		lines_out.push(parts.join("\t"));
+	}
+	if let Some((_, to)) = maybe_lines {
+		assert!(
+			to <= line_number,
+			"--lines goes up to {} but the lyrics only have {} lines",
+			to,
+			line_number
+		);
+	}
+	let mut retimed = lines_out.join("\n");
+	if text.ends_with('\n') {
+		retimed.push('\n');
+	}
+	retimed
+}
+
+// "01:02.50", "00:01:02,500", "00:01:02.500"
+fn parse_clock(raw: &str) -> Option<f64> {
+	let mut seconds = 0.0;
+	for part in raw.trim().replace(',', ".").split(':') {
+		seconds = seconds * 60.0 + part.parse::<f64>().ok()?;
+	}
+	Some(seconds)
+}
+
+// straight quotes and hyphens, since the tsv doesn't allow the curly ones (or parentheses at all)
+fn normalize_text(text: &str) -> String {
+	text.replace(['(', ')', '（', '）'], "")
+		.replace(['“', '”'], "\"")
+		.replace(['‘', '’'], "'")
+		.replace('–', "-")
+		.split_whitespace()
+		.collect::<Vec<_>>()
+		.join(" ")
+}
+
+// our own lrc/srt exports write "Name: " whenever the vocalists change
+fn split_speaker(text: &str, vocalists: &VocalistRegistry) -> (Vec<String>, String) {
+	if let Some((speaker, rest)) = text.split_once(": ") {
+		let names: Vec<&str> = speaker.split(", ").collect();
+		if names.iter().all(|name| vocalists.resolve(name).is_some()) {
+			return (
+				names.iter().map(|name| name.to_string()).collect(),
+				rest.to_string()
+			);
+		}
+	}
+	(Vec::new(), text.to_string())
+}
+
+enum LrcEvent {
+	Line(String),
+	Silence, // a timestamp with no text, which ends the line before it
+	Break    // a blank line in the file
+}
+
+fn parse_lrc(source: &str, vocalists: &VocalistRegistry) -> Vec<Vec<ImportedLine>> {
+	let mut events: Vec<(f64, LrcEvent)> = Vec::new();
+	let mut last_time = 0.0;
+	for raw_line in source.lines() {
+		let mut rest = raw_line.trim();
+		let mut times = Vec::new();
+		while let Some(after_bracket) = rest.strip_prefix('[') {
+			let Some((tag, after_tag)) = after_bracket.split_once(']') else {
+				break;
+			};
+			if let Some(time) = parse_clock(tag) {
+				times.push(time);
+			}
+			// [ar:...] and friends are skipped
+			rest = after_tag;
+		}
+		// enhanced lrc word timings
+		let mut raw_text = String::new();
+		let mut in_tag = false;
+		for character in rest.chars() {
+			match character {
+				'<' => in_tag = true,
+				'>' if in_tag => in_tag = false,
+				_ if !in_tag => raw_text.push(character),
+				_ => {}
+			}
+		}
+		let text = normalize_text(&raw_text);
+		if times.is_empty() {
+			if raw_line.trim().is_empty() {
+				events.push((last_time, LrcEvent::Break));
+			}
+			continue;
+		}
+		for time in times {
+			last_time = time;
+			events.push((
+				time,
+				if text.is_empty() {
+					LrcEvent::Silence
+				} else {
+					LrcEvent::Line(text.clone())
+				}
+			));
+		}
+	}
+	events.sort_by(|left, right| left.0.total_cmp(&right.0));
+
+	let mut stanzas: Vec<Vec<ImportedLine>> = vec![Vec::new()];
+	for (time, event) in events {
+		if !matches!(event, LrcEvent::Break)
+			&& let Some(previous) = stanzas.iter_mut().flatten().last()
+			&& previous.end.is_none()
+		{
+			previous.end = Some(time);
+		}
+		match event {
+			LrcEvent::Break => stanzas.push(Vec::new()),
+			LrcEvent::Silence => {}
+			LrcEvent::Line(text) => {
+				let (vocalists_here, text_without_speaker) = split_speaker(&text, vocalists);
+				push_line(
+					&mut stanzas,
+					ImportedLine {
+						start: time,
+						end: None,
+						text: text_without_speaker,
+						vocalists: vocalists_here
+					}
+				);
+			}
+		}
+	}
+	tidy(stanzas)
+}
+
+fn parse_srt(source: &str, vocalists: &VocalistRegistry) -> Vec<Vec<ImportedLine>> {
+	let mut stanzas: Vec<Vec<ImportedLine>> = vec![Vec::new()];
+	for block in source.replace('\r', "").split("\n\n") {
+		let mut lines = block.lines().filter(|line| !line.trim().is_empty());
+		let Some(mut timing) = lines.next() else {
+			continue;
+		};
+		if !timing.contains("-->") {
+			// cue number
+			timing = lines.next().unwrap_or_default();
+		}
+		let (start, end) = parse_cue_timing(timing);
+		let text = normalize_text(&lines.collect::<Vec<_>>().join(" "));
+		if text.is_empty() {
+			continue;
+		}
+		let (vocalists_here, text_without_speaker) = split_speaker(&text, vocalists);
+		push_line(
+			&mut stanzas,
+			ImportedLine {
+				start,
+				end: Some(end),
+				text: text_without_speaker,
+				vocalists: vocalists_here
+			}
+		);
+	}
+	tidy(stanzas)
+}
+
+fn parse_vtt(source: &str) -> Vec<Vec<ImportedLine>> {
+	let mut stanzas: Vec<Vec<ImportedLine>> = vec![Vec::new()];
+	for block in source.replace('\r', "").split("\n\n") {
+		let lines: Vec<&str> = block
+			.lines()
+			.filter(|line| !line.trim().is_empty())
+			.collect();
+		let Some(timing_index) = lines.iter().position(|line| line.contains("-->")) else {
+			continue; // WEBVTT header, NOTE, STYLE
+		};
+		let (start, end) = parse_cue_timing(lines[timing_index]);
+		let mut vocalists_here: Vec<String> = Vec::new();
+		let mut raw_text = String::new();
+		for cue_line in &lines[timing_index + 1..] {
+			let mut rest: &str = cue_line;
+			while let Some(tag_start) = rest.find('<') {
+				raw_text.push_str(&rest[..tag_start]);
+				let Some(tag_length) = rest[tag_start..].find('>') else {
+					break;
+				};
+				let tag = &rest[tag_start + 1..tag_start + tag_length];
+				// <v Name> or <v.class Name, Other Name>
+				if let Some(voice) = tag.strip_prefix('v')
+					&& let Some((_, names)) = voice.split_once(' ')
+				{
+					vocalists_here.extend(names.split(", ").map(|name| name.trim().to_string()));
+				}
+				rest = &rest[tag_start + tag_length + 1..];
+			}
+			raw_text.push_str(rest);
+			raw_text.push(' ');
+		}
+		let text = normalize_text(
+			&raw_text
+				.replace("&lt;", "<")
+				.replace("&gt;", ">")
+				.replace("&nbsp;", " ")
+				.replace("&amp;", "&")
+		);
+		if text.is_empty() {
+			continue;
+		}
+		push_line(
+			&mut stanzas,
+			ImportedLine {
+				start,
+				end: Some(end),
+				text,
+				vocalists: vocalists_here
+			}
+		);
+	}
+	tidy(stanzas)
+}
+
+// "00:00:01,000 --> 00:00:02,500" (vtt may have cue settings after)
+fn parse_cue_timing(timing: &str) -> (f64, f64) {
+	let (raw_start, raw_end) = timing
+		.split_once("-->")
+		.unwrap_or_else(|| panic!("Expected a cue timing, found \"{}\"", timing));
+	let raw_end_time = raw_end.split_whitespace().next().unwrap_or_default();
+	(
+		parse_clock(raw_start).unwrap_or_else(|| panic!("Invalid cue timing \"{}\"", timing)),
+		parse_clock(raw_end_time).unwrap_or_else(|| panic!("Invalid cue timing \"{}\"", timing))
+	)
+}
+
+// starts a new stanza if there's been a long enough silence
+fn push_line(stanzas: &mut Vec<Vec<ImportedLine>>, line: ImportedLine) {
+	if let Some(previous) = stanzas.iter().flatten().last()
+		&& let Some(previous_end) = previous.end
+		&& line.start - previous_end > IMPORT_STANZA_GAP_SECONDS
+		&& stanzas.last().is_some_and(|stanza| !stanza.is_empty())
+	{
+		stanzas.push(Vec::new());
+	}
+	if let Some(stanza) = stanzas.last_mut() {
+		stanza.push(line);
+	}
+}
+
+fn tidy(stanzas: Vec<Vec<ImportedLine>>) -> Vec<Vec<ImportedLine>> {
+	stanzas
+		.into_iter()
+		.filter(|stanza| !stanza.is_empty())
+		.collect()
+}
+
+fn to_tsv(
+	stanzas: &[Vec<ImportedLine>], language: &str, default_vocalist: &str, song_seconds: f64
+) -> String {
+	let lines: Vec<&ImportedLine> = stanzas.iter().flatten().collect();
+	let mut current_vocalists: Vec<String> = Vec::new();
+	let mut index = 0;
+	let mut out_stanzas: Vec<String> = Vec::new();
+	for stanza in stanzas {
+		let mut out_lines: Vec<String> = Vec::new();
+		for line in stanza {
+			let next_start = lines.get(index + 1).map(|next| next.start);
+			index += 1;
+			// overlapping cues get cut off where the next one starts
+			let end = line
+				.end
+				.unwrap_or_else(|| (line.start + IMPORT_LAST_LINE_SECONDS).min(song_seconds))
+				.min(next_start.unwrap_or(f64::INFINITY));
+			let mut row = format!("{:.6}\t{:.6}\t{}", line.start, end, line.text);
+			if index == 1 {
+				row.push_str(&format!("\tlanguage:{}", language));
+			}
+			let vocalists_here = if line.vocalists.is_empty() && current_vocalists.is_empty() {
+				vec![default_vocalist.to_string()]
+			} else if line.vocalists.is_empty() {
+				current_vocalists.clone()
+			} else {
+				line.vocalists.clone()
+			};
+			if vocalists_here != current_vocalists {
+				for vocalist in &vocalists_here {
+					row.push_str(&format!("\tvocalist:{}", vocalist));
+				}
+				current_vocalists = vocalists_here;
+			}
+			out_lines.push(row);
+		}
+		out_stanzas.push(out_lines.join("\n"));
+	}
+	out_stanzas.join("\n\n")
+}
+
+#[test]
+fn imported_formats_become_tsv() {
+	let vocalists = VocalistRegistry::from_json(&serde_json::json!([
+		{"name": "Astro"},
+		{"name": "Friend"}
+	]));
+	let lrc = parse_lrc(
+		"[ar:Astro]\n[00:01.00]First <00:01.50>line\n[00:02.00]Friend: Second line\n[00:03.00]\n\n[00:10.00]Third line",
+		&vocalists
+	);
+	assert_eq!(
+		to_tsv(&lrc, "en", "Astro", 60.0),
+		"1.000000\t2.000000\tFirst line\tlanguage:en\tvocalist:Astro\n2.000000\t3.000000\tSecond line\tvocalist:Friend\n\n10.000000\t15.000000\tThird line"
+	);
+	let vtt = parse_vtt(
+		"WEBVTT\n\n00:00:01.000 --> 00:00:02.000\n<v Astro, Friend>It’s both of us\n\n00:00:09.000 --> 00:00:10.000\n<v Astro>Just (me)"
+	);
+	assert_eq!(
+		to_tsv(&vtt, "en", "Astro", 60.0),
+		"1.000000\t2.000000\tIt's both of us\tlanguage:en\tvocalist:Astro\tvocalist:Friend\n\n9.000000\t10.000000\tJust me\tvocalist:Astro"
+	);
+}
+
+#[test]
+fn retiming_moves_every_timestamp() {
+	let tsv = "1.000000\t2.000000\tFirst\tlanguage:en\n\n3.000000\t4.000000\tSec|ond\tsync:3.000000,3.500000\n";
+	let shift = Retiming {
+		factor: 1.0,
+		pivot: 0.0,
+		shift: 1.5
+	};
+	assert_eq!(
+		retime_tsv(tsv, shift, Some((2, 2))),
+		"1.000000\t2.000000\tFirst\tlanguage:en\n\n4.500000\t5.500000\tSec|ond\tsync:4.500000,5.000000\n"
+	);
+	// 1s -> 2s and 3s -> 6s, so everything doubles and moves over
+	let anchored = Retiming::from_anchors((1.0, 2.0), (3.0, 6.0));
+	assert_eq!(
+		retime_tsv(tsv, anchored, None),
+		"2.000000\t4.000000\tFirst\tlanguage:en\n\n6.000000\t8.000000\tSec|ond\tsync:6.000000,7.000000\n"
+	);
+}
@@ -2,26 +2,57 @@ pub mod album;
 pub mod artwork;
 pub mod assist;
 pub mod audiocodec;
+pub mod credit;
 pub mod lyric;
+pub mod lyrictools;
+pub mod sample;
+pub mod schema;
 pub mod song;
 pub mod titlable;
+pub mod vocalist;
 
 use crate::globals;
-use crate::media::{album::Album, assist::Assist, song::Song};
+use crate::media::{album::Album, assist::Assist, song::Song, vocalist::VocalistRegistry};
+use crate::types::versionkind::VersionKind;
 
-pub fn get_music_data(json_path: &std::path::Path) -> (Vec<Album>, Vec<Song>, Vec<Assist>) {
-	globals::log_3("Parsing", "", "Discography JSON", globals::ANSI_GREEN);
+fn read_discog_json(json_path: &std::path::Path) -> serde_json::Value {
 	let file =
 		std::fs::File::open(json_path).unwrap_or_else(|_| panic!("Couldn't find discog.json"));
 	let reader = std::io::BufReader::new(file);
-	let json_value: serde_json::Value = serde_json::from_reader(reader)
-		.unwrap_or_else(|error| panic!("discog.json is invalid JSON: {}", error));
+	serde_json::from_reader(reader)
+		.unwrap_or_else(|error| panic!("discog.json is invalid JSON: {}", error))
+}
+
+// source/explicit-words.txt: one word per line, # for comments. the check is skipped if it's missing
+fn explicit_word_list() -> Vec<String> {
+	let location = globals::filezone()
+		.join("source")
+		.join("explicit-words")
+		.with_extension("txt");
+	match std::fs::read_to_string(location) {
+		Ok(text) => text
+			.lines()
+			.map(str::trim)
+			.filter(|line| !line.is_empty() && !line.starts_with('#'))
+			.map(str::to_lowercase)
+			.collect(),
+		Err(_) => Vec::new()
+	}
+}
+
+pub fn get_music_data(json_path: &std::path::Path) -> (Vec<Album>, Vec<Song>, Vec<Assist>) {
+	globals::log_3("Parsing", "", "Discography JSON", globals::ANSI_GREEN);
+	let json_value = read_discog_json(json_path);
 	let object = globals::map_with_only_these_keys(
 		&json_value,
 		"Discography",
-		&["albums", "remixes", "assists"]
+		&schema::keys(schema::DISCOGRAPHY)
 	);
-	let all_remixes: Vec<Song> = {
+	// no vocalists is fine until some lyrics need one
+	let vocalists = object
+		.get("vocalists")
+		.map_or_else(VocalistRegistry::default, VocalistRegistry::from_json);
+	let mut all_remixes: Vec<Song> = {
 		let mut remixes = Vec::new();
 		for remix_json in object
 			.get("remixes")
@@ -30,7 +61,7 @@ pub fn get_music_data(json_path: &std::path::Path) -> (Vec<Album>, Vec<Song>, Ve
 			.expect("discog.json \"remixes\" attribute is not an array")
 			.iter()
 		{
-			remixes.push(Song::from_json(remix_json, None));
+			remixes.push(Song::from_json(remix_json, None, &vocalists));
 		}
 		remixes
 	};
@@ -40,7 +71,7 @@ pub fn get_music_data(json_path: &std::path::Path) -> (Vec<Album>, Vec<Song>, Ve
 		.as_array()
 		.expect("discog.json \"albums\" attribute is not an array")
 		.iter()
-		.map(Album::from_json)
+		.map(|album_json| Album::from_json(album_json, &vocalists))
 		.collect();
 	let all_assists: Vec<Assist> = object
 		.get("assists")
@@ -58,6 +89,51 @@ pub fn get_music_data(json_path: &std::path::Path) -> (Vec<Album>, Vec<Song>, Ve
 		}
 	}
 
+	// temporary singles fold into the album track they become once that album is out
+	for single_index in 0..all_albums.len() {
+		let Some(target) = all_albums[single_index].temporary.clone() else {
+			continue;
+		};
+		assert!(
+			all_albums[single_index].is_single(),
+			"Album {} is marked temporary, but only singles can be",
+			all_albums[single_index].format_title()
+		);
+		let (album_index, song_index) = all_albums
+			.iter()
+			.enumerate()
+			.filter(|(_, album)| !album.is_single())
+			.find_map(|(album_index, album)| {
+				album
+					.songs
+					.iter()
+					.position(|song| song.slug == target && !song.bonus)
+					.map(|song_index| (album_index, song_index))
+			})
+			.unwrap_or_else(|| {
+				panic!(
+					"Temporary single {} becomes \"{}\", but no album track has that slug",
+					all_albums[single_index].format_title(),
+					target
+				)
+			});
+		assert!(
+			album_index > single_index,
+			"Temporary single {} has to come before the album it's folded into",
+			all_albums[single_index].format_title()
+		);
+		if !all_albums[album_index].unreleased {
+			all_albums[single_index].folded_into = Some((album_index, song_index));
+			// the track takes over the single's release date, so it shows up as a single for its album
+			let single_released = all_albums[single_index].released.clone();
+			let track = &mut all_albums[album_index].songs[song_index];
+			if !track.released_as_single {
+				track.released = single_released;
+				track.released_as_single = true;
+			}
+		}
+	}
+
 	// validation
 	for remix in &all_remixes {
 		assert!(
@@ -86,7 +162,7 @@ pub fn get_music_data(json_path: &std::path::Path) -> (Vec<Album>, Vec<Song>, Ve
 	check_slug_collision("8831");
 	check_slug_collision("font");
 	for album in &all_albums {
-		if album.single {
+		if album.is_single() {
 			assert!(
 				album.title == album.songs[0].title,
 				"Single cannot have two different titles: {}, {}",
@@ -99,7 +175,15 @@ pub fn get_music_data(json_path: &std::path::Path) -> (Vec<Album>, Vec<Song>, Ve
 				album.title,
 				album.songs[0].title
 			);
-			check_slug_collision(&album.slug);
+			// a temporary single may share its slug with the track it becomes, which claims it below
+			if album.temporary.as_ref() != Some(&album.slug) {
+				check_slug_collision(&album.slug);
+			}
+			assert!(
+				album.songs[0].previous_slugs.is_empty(),
+				"Single {} should list its previous slugs on the album, not the song",
+				album.format_title()
+			);
 			for i in 2..album.songs.len() {
 				assert!(
 					album.songs[i].bonus,
@@ -114,6 +198,19 @@ pub fn get_music_data(json_path: &std::path::Path) -> (Vec<Album>, Vec<Song>, Ve
 				check_slug_collision(&song.slug);
 			}
 		}
+		for previous_slug in &album.previous_slugs {
+			check_slug_collision(previous_slug);
+		}
+		for song in &album.songs {
+			assert!(
+				!song.bonus || song.previous_slugs.is_empty(),
+				"Bonus track {} has no public pages, so it can't have previous slugs",
+				song.format_title()
+			);
+			for previous_slug in &song.previous_slugs {
+				check_slug_collision(previous_slug);
+			}
+		}
 		if !album.unreleased {
 			for song in &album.songs {
 				assert!(
@@ -125,13 +222,17 @@ pub fn get_music_data(json_path: &std::path::Path) -> (Vec<Album>, Vec<Song>, Ve
 			}
 		}
 	}
+	for remix in &all_remixes {
+		check_slug_collision(&remix.slug);
+		for previous_slug in &remix.previous_slugs {
+			check_slug_collision(previous_slug);
+		}
+	}
+	for assist in &all_assists {
+		check_slug_collision(&assist.slug);
+	}
 	for album in &all_albums {
 		for song in &album.songs {
-			assert!(
-				!song.event,
-				"Album track {} must not be marked as an event",
-				song.format_title()
-			);
 			assert!(
 				song.artwork.is_some(),
 				"Non-remix song {} on album {} must have its own artwork or inherit from a parent (How did this manage to happen?)",
@@ -183,6 +284,35 @@ pub fn get_music_data(json_path: &std::path::Path) -> (Vec<Album>, Vec<Song>, Ve
 		);
 	}
 
+	let explicit_words = explicit_word_list();
+	for song in all_albums
+		.iter()
+		.flat_map(|album| album.songs.iter())
+		.chain(all_remixes.iter())
+	{
+		if let Some(lyrics) = &song.lyrics {
+			for warning in lyrics.warnings() {
+				globals::log_2(
+					"Warning",
+					format!("{}: {}", song.format_title(), warning),
+					globals::ANSI_RED
+				);
+			}
+			let found = lyrics.explicit_words(&explicit_words);
+			if !song.explicit.is_explicit() && !found.is_empty() {
+				globals::log_2(
+					"Warning",
+					format!(
+						"{} isn't marked explicit, but its lyrics say {}",
+						song.format_title(),
+						found.join(", ")
+					),
+					globals::ANSI_RED
+				);
+			}
+		}
+	}
+
 	// check for ascending release dates
 	assert!(
 		all_albums.is_sorted_by(|a, b| a.released <= b.released),
@@ -197,5 +327,137 @@ pub fn get_music_data(json_path: &std::path::Path) -> (Vec<Album>, Vec<Song>, Ve
 		"Assists are not sorted from oldest to newest"
 	);
 
+	link_versions(&mut all_albums, &mut all_remixes);
+	link_samples(&mut all_albums, &mut all_remixes);
+
 	(all_albums, all_remixes, all_assists)
 }
+
+// every song, and whether it has a link page to point at (bonus tracks and folded temporary singles don't)
+fn every_song<'a>(all_albums: &'a [Album], all_remixes: &'a [Song]) -> Vec<(&'a Song, bool)> {
+	all_albums
+		.iter()
+		.flat_map(|album| {
+			album
+				.songs
+				.iter()
+				.map(|song| (song, !song.bonus && album.folded_into.is_none()))
+		})
+		.chain(all_remixes.iter().map(|remix| (remix, true)))
+		.collect()
+}
+
+// checks that samples of our own songs point somewhere real, then tells those songs who sampled them
+fn link_samples(all_albums: &mut [Album], all_remixes: &mut [Song]) {
+	let sampled_by: Vec<Vec<(String, String)>> = {
+		let songs = every_song(all_albums, all_remixes);
+		let mut sampled_by = vec![Vec::new(); songs.len()];
+		for (index, (song, has_page)) in songs.iter().enumerate() {
+			for sampled_slug in song
+				.samples
+				.iter()
+				.filter_map(|sample| sample.slug.as_ref())
+			{
+				let sampled_index = songs
+					.iter()
+					.position(|(other, _)| other.slug == *sampled_slug)
+					.unwrap_or_else(|| {
+						panic!(
+							"{} samples \"{}\", but no song has that slug",
+							song.format_title(),
+							sampled_slug
+						)
+					});
+				assert!(
+					sampled_index != index,
+					"{} can't sample itself",
+					song.format_title()
+				);
+				assert!(
+					songs[sampled_index].1,
+					"{} samples {}, which has no page to link to",
+					song.format_title(),
+					songs[sampled_index].0.format_title()
+				);
+				let backlink = (song.slug.clone(), song.format_title());
+				if *has_page && !sampled_by[sampled_index].contains(&backlink) {
+					sampled_by[sampled_index].push(backlink);
+				}
+			}
+		}
+		sampled_by
+	};
+	for (song, backlinks) in all_albums
+		.iter_mut()
+		.flat_map(|album| album.songs.iter_mut())
+		.chain(all_remixes.iter_mut())
+		.zip(sampled_by)
+	{
+		song.sampled_by = backlinks;
+	}
+}
+
+// follows every version_of back to its original, then has each song list the rest of its family
+fn link_versions(all_albums: &mut [Album], all_remixes: &mut [Song]) {
+	let other_versions: Vec<Vec<(String, String, Option<VersionKind>)>> = {
+		// bonus tracks and folded temporary singles can be versions, but have no page to link to
+		let songs = every_song(all_albums, all_remixes);
+		let originals: Vec<Option<usize>> = songs
+			.iter()
+			.map(|(song, _)| {
+				song.version_of.as_ref().map(|(original_slug, _)| {
+					songs
+						.iter()
+						.position(|(other, _)| other.slug == *original_slug)
+						.unwrap_or_else(|| {
+							panic!(
+								"{} is a version of \"{}\", but no song has that slug",
+								song.format_title(),
+								original_slug
+							)
+						})
+				})
+			})
+			.collect();
+		let roots: Vec<usize> = (0..songs.len())
+			.map(|start| {
+				let mut current = start;
+				let mut steps = 0;
+				while let Some(original) = originals[current] {
+					current = original;
+					steps += 1;
+					assert!(
+						steps <= songs.len(),
+						"{} is part of a version_of cycle",
+						songs[start].0.format_title()
+					);
+				}
+				current
+			})
+			.collect();
+		(0..songs.len())
+			.map(|index| {
+				(0..songs.len())
+					.filter(|other| {
+						*other != index && roots[*other] == roots[index] && songs[*other].1
+					})
+					.map(|other| {
+						(
+							songs[other].0.slug.clone(),
+							songs[other].0.format_title(),
+							songs[other].0.version_of.as_ref().map(|(_, kind)| *kind)
+						)
+					})
+					.collect()
+			})
+			.collect()
+	};
+	for (song, versions) in all_albums
+		.iter_mut()
+		.flat_map(|album| album.songs.iter_mut())
+		.chain(all_remixes.iter_mut())
+		.zip(other_versions)
+	{
+		song.other_versions = versions;
+	}
+}
@@ -0,0 +1,83 @@
+use crate::globals;
+use crate::media::schema;
+use crate::types::{duration::Duration, isrc::ISRC};
+
+#[derive(Debug)]
+pub struct Sample {
+	pub artist: Option<String>,
+	pub title: String,
+	pub isrc: Option<ISRC>,
+	pub slug: Option<String>, // one of our own songs; checked once every song is known
+	pub at: Option<Duration>  // where it comes in, for mix tracklists
+}
+
+impl Sample {
+	fn from_json(val: &serde_json::Value) -> Sample {
+		let obj = globals::map_with_only_these_keys(val, "Sample", &schema::keys(schema::SAMPLE));
+		let text = |key: &str| -> Option<String> {
+			obj.get(key).map(|v| {
+				let string = v
+					.as_str()
+					.unwrap_or_else(|| panic!("Sample {} {} is not a string", val, key))
+					.to_string();
+				assert!(
+					!string.is_empty()
+						&& !string.starts_with(char::is_whitespace)
+						&& !string.ends_with(char::is_whitespace),
+					"Sample {} {} is empty or has untrimmed whitespace",
+					val,
+					key
+				);
+				string
+			})
+		};
+		Sample {
+			artist: text("artist"),
+			title: text("title").unwrap_or_else(|| panic!("Sample {} has no title", val)),
+			isrc: text("isrc").map(|isrc| {
+				ISRC::from(&isrc)
+					.unwrap_or_else(|| panic!("Sample {} has an invalid ISRC \"{}\"", val, isrc))
+			}),
+			slug: text("slug").inspect(|slug| globals::check_custom_slug(slug)),
+			at: text("at").map(|at| {
+				Duration::from_timestamp(&at).unwrap_or_else(|| {
+					panic!(
+						"Sample {} has a poorly formed \"at\" (use M:SS or H:MM:SS)",
+						val
+					)
+				})
+			})
+		}
+	}
+	pub fn list_from_json(val: &serde_json::Value, what: &str, duration: &Duration) -> Vec<Sample> {
+		let samples: Vec<Sample> = val
+			.as_array()
+			.unwrap_or_else(|| panic!("{} \"samples\" attribute is not an array", what))
+			.iter()
+			.map(Sample::from_json)
+			.collect();
+		let timestamps: Vec<u32> = samples
+			.iter()
+			.filter_map(|sample| sample.at.map(|at| at.milliseconds()))
+			.collect();
+		assert!(
+			timestamps.is_sorted(),
+			"{} has sample timestamps out of order",
+			what
+		);
+		assert!(
+			timestamps
+				.last()
+				.is_none_or(|last| *last < duration.milliseconds()),
+			"{} has a sample timestamp after it ends",
+			what
+		);
+		samples
+	}
+	pub fn format_title(&self) -> String {
+		match &self.artist {
+			Some(artist) => format!("{} – {}", artist, self.title),
+			None => self.title.clone()
+		}
+	}
+}
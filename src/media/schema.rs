@@ -0,0 +1,509 @@
+// the shape of discog.json, all in one place. the from_json functions only accept the keys listed here,
+// and `distri schema` writes it out as a json schema so editors can autocomplete and check discog.json
+
+use crate::fileops;
+use crate::globals;
+use crate::media::credit::Role;
+use crate::types::{
+	assistrole::AssistRole, color::PALETTE_MODES, genre::Genre, language::Language,
+	releasetype::ReleaseType, urlset::UrlSet, versionkind::VersionKind
+};
+
+pub enum Kind {
+	Text,
+	Flag,
+	Slug,
+	Date,      // YYYY-MM-DD
+	Time,      // HH:MM
+	Timezone,  // +09:00, -05:00, or Z
+	Timestamp, // M:SS or H:MM:SS
+	Isrc,
+	Upc,
+	Link, // https only
+	Hex,
+	Artists,  // one artist entry, or a list of them
+	Advisory, // true, false, or "clean"
+	Artwork,  // true, or the name of the artwork
+	Discs,    // [track count, disc name, track count, disc name, ...]
+	Choice(fn() -> Vec<&'static str>),
+	ListOf(&'static Kind),
+	Object(&'static str) // one of the definitions in discog_schema
+}
+
+pub struct Field {
+	pub key: &'static str,
+	pub required: bool,
+	pub kind: Kind,
+	pub about: &'static str
+}
+
+const fn required(key: &'static str, kind: Kind, about: &'static str) -> Field {
+	Field {
+		key,
+		required: true,
+		kind,
+		about
+	}
+}
+
+const fn optional(key: &'static str, kind: Kind, about: &'static str) -> Field {
+	Field {
+		key,
+		required: false,
+		kind,
+		about
+	}
+}
+
+fn genres() -> Vec<&'static str> {
+	Genre::ALL.iter().map(Genre::to_string).collect()
+}
+fn album_release_types() -> Vec<&'static str> {
+	ReleaseType::ALL
+		.iter()
+		.filter(|release_type| release_type.allowed_for_albums())
+		.map(|release_type| release_type.to_string())
+		.collect()
+}
+fn song_release_types() -> Vec<&'static str> {
+	ReleaseType::ALL
+		.iter()
+		.filter(|release_type| release_type.allowed_for_standalone_songs())
+		.map(|release_type| release_type.to_string())
+		.collect()
+}
+fn version_kinds() -> Vec<&'static str> {
+	VersionKind::ALL
+		.iter()
+		.map(|kind| kind.to_string())
+		.collect()
+}
+fn credit_roles() -> Vec<&'static str> {
+	Role::ALL.iter().map(|role| role.to_string()).collect()
+}
+fn assist_roles() -> Vec<&'static str> {
+	AssistRole::ALL.iter().map(AssistRole::to_string).collect()
+}
+fn palette_modes() -> Vec<&'static str> {
+	PALETTE_MODES.to_vec()
+}
+// lyric tsvs take either code
+fn language_codes() -> Vec<&'static str> {
+	let mut codes: Vec<&'static str> = Language::ALL
+		.iter()
+		.flat_map(|language| [language.iso_639_1(), language.iso_639_2()])
+		.filter(|code| !code.is_empty())
+		.collect();
+	codes.sort_unstable();
+	codes.dedup();
+	codes
+}
+
+pub const DISCOGRAPHY: &[Field] = &[
+	optional("$schema", Kind::Text, "Where editors can find this schema"),
+	required(
+		"albums",
+		Kind::ListOf(&Kind::Object("Album")),
+		"Albums, oldest first"
+	),
+	required(
+		"remixes",
+		Kind::ListOf(&Kind::Object("Song")),
+		"Songs not on an album, oldest first"
+	),
+	required(
+		"assists",
+		Kind::ListOf(&Kind::Object("Assist")),
+		"Other people's releases, oldest first"
+	),
+	optional(
+		"vocalists",
+		Kind::ListOf(&Kind::Object("Vocalist")),
+		"Everyone who can be credited for vocals in lyrics"
+	)
+];
+
+pub const ALBUM: &[Field] = &[
+	required("title", Kind::Text, "Album title"),
+	optional("artist", Kind::Artists, "\"Astro\" if left out"),
+	optional(
+		"release_type",
+		Kind::Choice(album_release_types),
+		"\"album\" if left out"
+	),
+	required(
+		"released",
+		Kind::Date,
+		"Release date; a future date schedules it"
+	),
+	optional("release_time", Kind::Time, "Time of day it comes out"),
+	optional("timezone", Kind::Timezone, "UTC offset release_time is in"),
+	optional("unreleased", Kind::Flag, "Hold it back no matter the date"),
+	optional("bcid", Kind::Text, "Bandcamp album id for embeds"),
+	required(
+		"url",
+		Kind::Object("Url"),
+		"Links to it on streaming platforms"
+	),
+	required("genre", Kind::Choice(genres), "Genre"),
+	required(
+		"color",
+		Kind::Object("Color"),
+		"Palette that goes with the artwork"
+	),
+	optional(
+		"songs",
+		Kind::ListOf(&Kind::Object("Song")),
+		"Tracklist, bonus tracks last"
+	),
+	optional(
+		"discs",
+		Kind::Discs,
+		"Track count and name of each disc, alternating"
+	),
+	optional(
+		"about",
+		Kind::Text,
+		"Description, with paragraphs separated by blank lines"
+	),
+	optional("upc", Kind::Upc, "Album UPC"),
+	optional(
+		"credits",
+		Kind::ListOf(&Kind::Object("Credit")),
+		"Credits for every song on it"
+	),
+	optional(
+		"slug",
+		Kind::Slug,
+		"Custom slug instead of one from the title"
+	),
+	optional(
+		"previous_slugs",
+		Kind::ListOf(&Kind::Slug),
+		"Slugs it used to have; they redirect here"
+	),
+	optional(
+		"temporary",
+		Kind::Slug,
+		"Singles only: the upcoming album track this single becomes"
+	)
+];
+
+pub const SONG: &[Field] = &[
+	required(
+		"title",
+		Kind::Text,
+		"Song title, with remixers and features"
+	),
+	optional("artist", Kind::Artists, "\"Astro\" if left out"),
+	optional(
+		"released",
+		Kind::Date,
+		"Release date; required for songs not on an album"
+	),
+	optional("release_time", Kind::Time, "Time of day it comes out"),
+	optional("timezone", Kind::Timezone, "UTC offset release_time is in"),
+	optional("unreleased", Kind::Flag, "Hold it back no matter the date"),
+	optional(
+		"release_type",
+		Kind::Choice(song_release_types),
+		"Songs not on an album only; \"remix\" if left out"
+	),
+	optional("bonus", Kind::Flag, "Bandcamp-exclusive bonus track"),
+	optional(
+		"explicit",
+		Kind::Advisory,
+		"true, false, or \"clean\" for an edited version"
+	),
+	optional("isrc", Kind::Isrc, "Track ISRC"),
+	optional(
+		"lyrics",
+		Kind::Flag,
+		"Whether source/lyrics has a tsv for it"
+	),
+	optional(
+		"color",
+		Kind::Object("Color"),
+		"Palette; songs on albums can inherit it"
+	),
+	optional(
+		"url",
+		Kind::Object("Url"),
+		"Links to it on streaming platforms"
+	),
+	optional(
+		"artwork",
+		Kind::Artwork,
+		"Its own artwork: true if named after the song, or the artwork's name"
+	),
+	optional(
+		"samples",
+		Kind::ListOf(&Kind::Object("Sample")),
+		"What it samples; the tracklist for a dj mix"
+	),
+	optional(
+		"version_of",
+		Kind::Object("Version"),
+		"The song this is a version of"
+	),
+	optional(
+		"about",
+		Kind::Text,
+		"Description, with paragraphs separated by blank lines"
+	),
+	optional(
+		"genre",
+		Kind::Choice(genres),
+		"Genre; required for songs not on an album, not allowed on one"
+	),
+	optional(
+		"credits",
+		Kind::ListOf(&Kind::Object("Credit")),
+		"Credits for this song"
+	),
+	optional(
+		"slug",
+		Kind::Slug,
+		"Custom slug instead of one from the title"
+	),
+	optional(
+		"previous_slugs",
+		Kind::ListOf(&Kind::Slug),
+		"Slugs it used to have; they redirect here"
+	)
+];
+
+pub const ASSIST: &[Field] = &[
+	required(
+		"titlable",
+		Kind::Text,
+		"The release, as \"[Artist] – [Title]\""
+	),
+	required("released", Kind::Date, "Release date"),
+	required("url", Kind::Link, "Where the release lives"),
+	required("role", Kind::Choice(assist_roles), "What i did on it"),
+	optional(
+		"slug",
+		Kind::Slug,
+		"Custom slug instead of one from titlable"
+	),
+	optional("color", Kind::Object("Color"), "Palette for its page")
+];
+
+pub const VOCALIST: &[Field] = &[
+	required("name", Kind::Text, "Name to credit everywhere"),
+	optional(
+		"aliases",
+		Kind::ListOf(&Kind::Text),
+		"Other names lyric tsvs may use"
+	),
+	optional("url", Kind::Link, "Link for link pages")
+];
+
+pub const COLOR: &[Field] = &[
+	required("foreground", Kind::Hex, "Text color"),
+	required("background", Kind::Hex, "Background color"),
+	required("accent", Kind::Hex, "Color for larger or bolder text"),
+	optional(
+		"mode",
+		Kind::Choice(palette_modes),
+		"Show platform logos in black or white instead of color"
+	)
+];
+
+pub const ARTIST: &[Field] = &[
+	required("name", Kind::Text, "The artist's name"),
+	optional(
+		"one_artist",
+		Kind::Flag,
+		"This name has \"and\" in it, but it's still one artist"
+	)
+];
+
+pub const CREDIT: &[Field] = &[
+	required("role", Kind::Choice(credit_roles), "What they did"),
+	required("name", Kind::Text, "Who it is")
+];
+
+pub const SAMPLE: &[Field] = &[
+	required("title", Kind::Text, "Title of what's sampled"),
+	optional("artist", Kind::Text, "Who it's by"),
+	optional("isrc", Kind::Isrc, "ISRC of what's sampled"),
+	optional("slug", Kind::Slug, "One of our own songs with a link page"),
+	optional("at", Kind::Timestamp, "Where it comes in")
+];
+
+pub const VERSION: &[Field] = &[
+	required("slug", Kind::Slug, "Slug of the song this is a version of"),
+	required(
+		"kind",
+		Kind::Choice(version_kinds),
+		"What kind of version it is"
+	)
+];
+
+pub fn keys(fields: &[Field]) -> Vec<&'static str> {
+	fields.iter().map(|field| field.key).collect()
+}
+
+fn kind_schema(kind: &Kind) -> serde_json::Value {
+	let pattern = |pattern: &str| serde_json::json!({"type": "string", "pattern": pattern});
+	match kind {
+		Kind::Text => serde_json::json!({"type": "string"}),
+		Kind::Flag => serde_json::json!({"type": "boolean"}),
+		Kind::Slug => pattern("^[a-z0-9-]+$"),
+		Kind::Date => pattern("^[0-9]{4}-[0-9]{2}-[0-9]{2}$"),
+		Kind::Time => pattern("^([01][0-9]|2[0-3]):[0-5][0-9]$"),
+		Kind::Timezone => pattern("^(Z|[+-][0-9]{2}:[0-5][0-9])$"),
+		Kind::Timestamp => pattern("^([0-9]+:)?[0-9]+:[0-5][0-9]$"),
+		Kind::Isrc => pattern("^[A-Z]{2}[A-Z0-9]{3}[0-9]{7}$"),
+		Kind::Upc => pattern("^[0-9]{12}$"),
+		Kind::Link => pattern("^https://[^\\s]+$"),
+		Kind::Hex => pattern("^#[0-9A-Fa-f]{6}$"),
+		Kind::Artists => {
+			let entry =
+				serde_json::json!({"oneOf": [{"type": "string"}, {"$ref": "#/$defs/Artist"}]});
+			serde_json::json!({
+				"oneOf": [entry, {"type": "array", "items": entry, "minItems": 1}]
+			})
+		}
+		Kind::Advisory => serde_json::json!({"enum": [true, false, "clean"]}),
+		Kind::Artwork => serde_json::json!({
+			"oneOf": [{"const": true}, {"type": "string", "pattern": "^[a-z0-9-]+$"}]
+		}),
+		Kind::Discs => serde_json::json!({
+			"type": "array",
+			"items": {"type": ["integer", "string"]}
+		}),
+		Kind::Choice(choices) => serde_json::json!({"enum": choices()}),
+		Kind::ListOf(inner) => serde_json::json!({"type": "array", "items": kind_schema(inner)}),
+		Kind::Object(name) => serde_json::json!({"$ref": format!("#/$defs/{}", name)})
+	}
+}
+
+fn object_schema(fields: &[Field]) -> serde_json::Value {
+	let properties: serde_json::Map<String, serde_json::Value> = fields
+		.iter()
+		.map(|field| {
+			let mut property = kind_schema(&field.kind);
+			let _ = property
+				.as_object_mut()
+				.unwrap_or_else(|| panic!("Schema for \"{}\" is not an object", field.key))
+				.insert(String::from("description"), serde_json::json!(field.about));
+			(field.key.to_string(), property)
+		})
+		.collect();
+	let required: Vec<&str> = fields
+		.iter()
+		.filter(|field| field.required)
+		.map(|field| field.key)
+		.collect();
+	serde_json::json!({
+		"type": "object",
+		"properties": properties,
+		"required": required,
+		"additionalProperties": false
+	})
+}
+
+pub fn discog_schema() -> serde_json::Value {
+	let url_properties: serde_json::Map<String, serde_json::Value> = UrlSet::PLATFORMS
+		.iter()
+		.map(|platform| (platform.to_string(), kind_schema(&Kind::Link)))
+		.collect();
+	let mut schema = object_schema(DISCOGRAPHY);
+	let object = schema
+		.as_object_mut()
+		.unwrap_or_else(|| panic!("Discography schema is not an object"));
+	let _ = object.insert(
+		String::from("$schema"),
+		serde_json::json!("https://json-schema.org/draft/2020-12/schema")
+	);
+	let _ = object.insert(String::from("title"), serde_json::json!("discog.json"));
+	let _ = object.insert(
+		String::from("$defs"),
+		serde_json::json!({
+			"Album": object_schema(ALBUM),
+			"Song": object_schema(SONG),
+			"Assist": object_schema(ASSIST),
+			"Vocalist": object_schema(VOCALIST),
+			"Color": object_schema(COLOR),
+			"Artist": object_schema(ARTIST),
+			"Credit": object_schema(CREDIT),
+			"Sample": object_schema(SAMPLE),
+			"Version": object_schema(VERSION),
+			"Url": {
+				"type": "object",
+				"properties": url_properties,
+				"additionalProperties": false
+			},
+			// not in discog.json itself, but it's what language: tags in lyric tsvs take
+			"Language": {"enum": language_codes()}
+		})
+	);
+	schema
+}
+
+// `distri schema`: writes source/discog.schema.json, which discog.json can point to with "$schema"
+pub fn distri_schema() {
+	let location = globals::filezone()
+		.join("source")
+		.join("discog.schema.json"); // with_extension would make this discog.json
+	fileops::write_file(
+		&location,
+		serde_json::to_string_pretty(&discog_schema()).expect("Couldn't serialize schema")
+	);
+	globals::log_2("Wrote", location.display(), globals::ANSI_GREEN);
+}
+
+#[test]
+fn example_only_uses_known_keys() {
+	let example: serde_json::Value =
+		serde_json::from_str(include_str!("../../discog-example.json"))
+			.unwrap_or_else(|error| panic!("discog-example.json is invalid JSON: {}", error));
+	let check = |val: &serde_json::Value, fields: &[Field], what: &str| {
+		for key in val.as_object().into_iter().flat_map(|obj| obj.keys()) {
+			assert!(
+				fields.iter().any(|field| field.key == key),
+				"discog-example.json has {} key \"{}\" that isn't in the schema",
+				what,
+				key
+			);
+		}
+	};
+	let list = |key: &str| {
+		example
+			.get(key)
+			.and_then(|v| v.as_array())
+			.into_iter()
+			.flatten()
+	};
+	check(&example, DISCOGRAPHY, "top-level");
+	for album in list("albums") {
+		check(album, ALBUM, "album");
+	}
+	let album_songs = list("albums").flat_map(|album| {
+		album
+			.get("songs")
+			.and_then(|v| v.as_array())
+			.into_iter()
+			.flatten()
+	});
+	for song in album_songs.chain(list("remixes")) {
+		check(song, SONG, "song");
+		for sample in song
+			.get("samples")
+			.and_then(|v| v.as_array())
+			.into_iter()
+			.flatten()
+		{
+			check(sample, SAMPLE, "sample");
+		}
+	}
+	for assist in list("assists") {
+		check(assist, ASSIST, "assist");
+	}
+	for vocalist in list("vocalists") {
+		check(vocalist, VOCALIST, "vocalist");
+	}
+}
@@ -1,23 +1,37 @@
 use crate::build::smartquotes;
 use crate::globals;
 use crate::media::{
-	album::Album, artwork::Artwork, audiocodec::AudioCodec, lyric, lyric::Lyrics,
-	titlable::Titlable
+	album::Album, artwork::Artwork, audiocodec::AudioCodec, credit, credit::Credit, credit::Role,
+	lyric, lyric::LyricContext, lyric::Lyrics, sample::Sample, schema, titlable::Titlable,
+	vocalist::VocalistRegistry
 };
 use crate::types::{
-	color::Palette, date::Date, duration::Duration, genre::Genre, isrc::ISRC, urlset::UrlSet
+	advisory::Advisory,
+	color::Palette,
+	date::{Date, ReleaseTime},
+	duration::Duration,
+	genre::Genre,
+	isrc::ISRC,
+	releasetype::ReleaseType,
+	urlset::UrlSet,
+	versionkind::VersionKind
 };
 
 #[derive(Debug)]
 pub struct Song {
 	pub slug: String,
+	pub previous_slugs: Vec<String>, // each one gets redirected to slug
 	pub parent_album_indices: Option<(usize, usize)>, // album-index, position in tracklist
 	pub title: String,
-	pub artist: String,
-	pub released: Date, // may inherit from parent
+	pub artist: String, // every artist, joined for display
+	pub artists: Vec<String>,
+	pub released: Date,            // may inherit from parent
+	pub release_time: ReleaseTime, // may inherit from parent
 	pub released_as_single: bool,
 	pub bonus: bool,
-	pub event: bool,
+	pub release_type: ReleaseType, // MUST inherit from parent if on an album
+	pub explicit: Advisory,
+	pub credits: Vec<Credit>, // only this song's; see all_credits for the album's too
 	pub artwork: Option<Artwork>, // songs on albums inherit from parents; remixes have None
 	pub duration: Duration,
 	pub isrc: Option<ISRC>,
@@ -26,56 +40,25 @@ pub struct Song {
 	pub genre: Genre,     // MUST inherit from parent if on an album
 	pub unreleased: bool, // may inherit from parent
 	pub url: UrlSet,
-	pub samples: Option<Vec<String>>, // report as "Mix tracklist" if event
+	pub samples: Vec<Sample>, // report as "Mix tracklist" if a dj mix
+	pub sampled_by: Vec<(String, String)>, // (slug, title) of our songs that sample this one. filled in once every song is known
+	pub version_of: Option<(String, VersionKind)>, // slug of the original, and what this is to it
+	pub other_versions: Vec<(String, String, Option<VersionKind>)>, // (slug, title, kind); None for the original. filled in once every song is known
 	pub about: Option<Vec<String>>
 }
 
 impl Song {
-	pub fn from_json(val: &serde_json::Value, parent_album: Option<&Album>) -> Song {
-		let obj = globals::map_with_only_these_keys(
-			val,
-			"Song",
-			&[
-				"artist",
-				"title",
-				"released",
-				"bonus",
-				"event",
-				"isrc",
-				"lyrics",
-				"color",
-				"url",
-				"samples",
-				"about",
-				"artwork",
-				"unreleased",
-				"genre",
-				"slug"
-			]
-		);
+	pub fn from_json(
+		val: &serde_json::Value, parent_album: Option<&Album>, vocalists: &VocalistRegistry
+	) -> Song {
+		let obj = globals::map_with_only_these_keys(val, "Song", &schema::keys(schema::SONG));
 		let url_set = match obj.get("url") {
 			None => UrlSet::empty(),
 			Some(val_for_url) => UrlSet::from(val_for_url)
 		};
 
-		let artist = match obj.get("artist") {
-			None => "Astro".to_string(),
-			Some(val_for_artist) => {
-				let artist = val_for_artist.as_str().unwrap_or_else(|| {
-					panic!(
-						"Song JSON attribute \"artist\" is not a string: {}",
-						val_for_artist
-					)
-				});
-				assert!(
-					!artist.starts_with(char::is_whitespace)
-						&& !artist.ends_with(char::is_whitespace),
-					"Song JSON has poorly formed \"artist\" string: {}",
-					artist
-				);
-				artist.to_string()
-			}
-		};
+		let artists = globals::parse_artists(obj.get("artist"), "Song");
+		let artist = artists.join(globals::ARTIST_SEPARATOR);
 
 		let title = {
 			let title_object = obj
@@ -101,11 +84,15 @@ impl Song {
 				string.to_string()
 			}
 			Some(other) => panic!("Custom slug \"{}\" is not a string", other),
-			None => globals::compute_slug(&artist, &title)
+			None => globals::compute_slug(&artists, &title)
 		};
 
-		let song = Song {
+		let duration =
+			Duration::from_audio_file_and_validate(parent_album.map(|a| a.slug.as_str()), &slug);
+
+		let mut song = Song {
 			parent_album_indices: None,
+			previous_slugs: globals::parse_previous_slugs(obj.get("previous_slugs"), "Song"),
 			artwork: match obj.get("artwork") {
 				None => parent_album.map(|album| album.artwork.clone()),
 				Some(val_for_artwork) => match val_for_artwork {
@@ -165,8 +152,17 @@ impl Song {
 						panic!("Song JSON attribute \"unreleased\" is not a bool: {}", v);
 					})
 				})
-				.unwrap_or_else(|| parent_album.map(|album| album.unreleased).unwrap_or(false)),
+				.unwrap_or_else(|| {
+					// songs with their own date (singles ahead of the album) go by that date instead
+					obj.get("released").is_none()
+						&& parent_album.map(|album| album.unreleased).unwrap_or(false)
+				}),
 			released_as_single: obj.get("released").is_some(),
+			release_time: ReleaseTime::from_json(
+				obj,
+				parent_album.map(|album| &album.release_time),
+				"Song"
+			),
 			bonus: match obj.get("bonus") {
 				None => false,
 				Some(val_for_bonus) => val_for_bonus.as_bool().unwrap_or_else(|| {
@@ -176,19 +172,15 @@ impl Song {
 					)
 				})
 			},
-			event: match obj.get("event") {
-				None => false,
-				Some(val_for_event) => val_for_event.as_bool().unwrap_or_else(|| {
-					panic!(
-						"Song JSON attribute \"event\" is not a boolean: {}",
-						val_for_event
-					)
-				})
-			},
-			duration: Duration::from_audio_file_and_validate(
-				parent_album.map(|a| a.slug.as_str()),
-				&slug
-			),
+			explicit: obj
+				.get("explicit")
+				.map(Advisory::from_json)
+				.unwrap_or(Advisory::NotExplicit),
+			credits: obj
+				.get("credits")
+				.map(|credits_json| Credit::list_from_json(credits_json, &title))
+				.unwrap_or_default(),
+			duration,
 			isrc: obj.get("isrc").map(|v| {
 				let isrc = v.as_str().unwrap_or_else(|| {
 					panic!("Song JSON attribute \"isrc\" is not a string: {}", v)
@@ -209,15 +201,10 @@ impl Song {
 					)
 				})
 			} {
-				let lyrics_location = {
-					let mut location = globals::filezone().join("source").join("lyrics");
-					if let Some(album) = parent_album {
-						location = location.join(&album.slug);
-					}
-					location.join(&slug).with_extension("tsv")
-				};
+				let lyrics_location =
+					lyric::lyrics_location(parent_album.map(|album| album.slug.as_str()), &slug);
 				match std::fs::read_to_string(lyrics_location) {
-					Ok(text) => Some(Lyrics::from(&text)),
+					Ok(text) => Some(Lyrics::from(&text, vocalists, duration)),
 					Err(_) => {
 						panic!(
 							"Couldn't read lyrics text {}{}.tsv",
@@ -248,28 +235,32 @@ impl Song {
 						.clone()
 				}),
 			url: url_set,
-			samples: obj.get("samples").map(|v| {
-				let arr = v.as_array().unwrap_or_else(|| {
-					panic!("Song JSON attribute \"samples\" is not an array: {}", v)
-				});
-				arr.iter()
-					.map(|s| {
-						let sample = s
-							.as_str()
-							.unwrap_or_else(|| {
-								panic!("Song JSON \"samples\" element is not a string: {}", s)
-							})
-							.to_owned();
-						assert!(
-							!sample.starts_with(char::is_whitespace)
-								&& !sample.ends_with(char::is_whitespace),
-							"Song JSON \"samples\" element is poorly formed string: \"{}\"",
-							sample
-						);
-						sample
-					})
-					.collect()
+			version_of: obj.get("version_of").map(|val_for_version_of| {
+				let version_obj = globals::map_with_only_these_keys(
+					val_for_version_of,
+					"Version",
+					&schema::keys(schema::VERSION)
+				);
+				let field = |key: &str| {
+					version_obj
+						.get(key)
+						.and_then(|v| v.as_str())
+						.unwrap_or_else(|| {
+							panic!(
+								"Song JSON attribute \"version_of\" needs a string \"{}\": {}",
+								key, val_for_version_of
+							)
+						})
+				};
+				globals::check_custom_slug(field("slug"));
+				(field("slug").to_string(), VersionKind::from(field("kind")))
 			}),
+			other_versions: Vec::new(),
+			samples: obj
+				.get("samples")
+				.map(|v| Sample::list_from_json(v, &title, &duration))
+				.unwrap_or_default(),
+			sampled_by: Vec::new(),
 			about: obj.get("about").map(|v| {
 				let string = v
 					.as_str()
@@ -312,19 +303,158 @@ impl Song {
 					genre_string
 				)
 			},
+			release_type: match (
+				parent_album,
+				obj.get("release_type").map(|s| {
+					s.as_str().unwrap_or_else(|| {
+						panic!(
+							"Song JSON attribute \"release_type\" is not a string: {}",
+							s
+						)
+					})
+				})
+			) {
+				(None, None) => ReleaseType::Remix,
+				(None, Some(release_type_string)) => {
+					let release_type = ReleaseType::from(release_type_string);
+					assert!(
+						release_type.allowed_for_standalone_songs(),
+						"Song {} can't be released on its own as a {}",
+						title,
+						release_type.label()
+					);
+					release_type
+				}
+				(Some(album), None) => album.release_type,
+				(Some(album), Some(release_type_string)) => panic!(
+					"Song on album {} must not specify its own release type {}",
+					album.format_title(),
+					release_type_string
+				)
+			},
 			slug,
 			artist,
+			artists,
 			title
 		};
 
+		// scheduled releases come out on their own, on the first build after their release_time in their timezone
+		if !song.release_time.has_arrived(&song.released) {
+			song.unreleased = true;
+		}
+
 		assert!(!smartquotes::contains_smart_quotes(&song.title));
 		assert!(!smartquotes::contains_smart_quotes(&song.artist));
+		for featured in credit::names_for(&song.credits, Role::Featured) {
+			assert!(
+				song.title.contains(featured),
+				"Song {} credits {} as featured, but the title doesn't say so",
+				song.title,
+				featured
+			);
+		}
+		// a dj mix's timestamps are its chapters, so they have to cover the whole thing
+		if song.release_type == ReleaseType::DjMix
+			&& song.samples.iter().any(|sample| sample.at.is_some())
+		{
+			assert!(
+				song.samples.iter().all(|sample| sample.at.is_some()),
+				"DJ mix {} has timestamps for only some of its tracklist",
+				song.format_title()
+			);
+			assert!(
+				song.samples[0].at.is_some_and(|at| at.milliseconds() == 0),
+				"DJ mix {} tracklist has to start at 0:00",
+				song.format_title()
+			);
+			assert!(
+				song.samples
+					.windows(2)
+					.all(|pair| pair[0].at.map(|at| at.milliseconds())
+						< pair[1].at.map(|at| at.milliseconds())),
+				"DJ mix {} has two tracks starting at the same time",
+				song.format_title()
+			);
+		}
 
 		song
 	}
+	// (start, end, sample) for each track of a timestamped dj mix tracklist; empty for anything else
+	pub fn chapters(&self) -> Vec<(Duration, Duration, &Sample)> {
+		if self.release_type != ReleaseType::DjMix {
+			return Vec::new();
+		}
+		let starts: Vec<(Duration, &Sample)> = self
+			.samples
+			.iter()
+			.filter_map(|sample| sample.at.map(|at| (at, sample)))
+			.collect();
+		starts
+			.iter()
+			.enumerate()
+			.map(|(index, (start, sample))| {
+				let end = starts
+					.get(index + 1)
+					.map_or(self.duration, |(next_start, _)| *next_start);
+				(*start, end, *sample)
+			})
+			.collect()
+	}
+	// FILE is named the way the link page's download attribute names the audio
+	pub fn cue_sheet(&self, codec: &AudioCodec) -> String {
+		// cue sheets can't escape quotes
+		let quote = |text: &str| format!("\"{}\"", text.replace('"', "'"));
+		let mut cue = format!(
+			"REM GENRE {}\nREM DATE {}\nPERFORMER {}\nTITLE {}\nFILE {} {}\n",
+			quote(self.genre.to_string()),
+			self.released.year,
+			quote(&self.artist),
+			quote(&self.title),
+			quote(&format!("{}.{}", self.format_title(), codec.ext())),
+			match codec {
+				AudioCodec::Mp3 => "MP3",
+				AudioCodec::Flac => "WAVE"
+			}
+		);
+		for (index, (start, _, sample)) in self.chapters().iter().enumerate() {
+			cue.push_str(&format!("  TRACK {:02} AUDIO\n", index + 1));
+			cue.push_str(&format!("    TITLE {}\n", quote(&sample.title)));
+			if let Some(artist) = &sample.artist {
+				cue.push_str(&format!("    PERFORMER {}\n", quote(artist)));
+			}
+			if let Some(isrc) = &sample.isrc {
+				cue.push_str(&format!("    ISRC {}\n", isrc.as_dense()));
+			}
+			cue.push_str(&format!("    INDEX 01 {}\n", start.cue_index()));
+		}
+		cue
+	}
 	pub fn public_filename(&self) -> String {
 		Titlable::Song(self).public_filename()
 	}
+	// album credits apply to every song on it
+	pub fn all_credits(&self, all_albums: &[Album]) -> Vec<Credit> {
+		let mut credits = self.credits.clone();
+		if let Some((album_index, _)) = self.parent_album_indices {
+			for album_credit in &all_albums[album_index].credits {
+				if !credits.contains(album_credit) {
+					credits.push(album_credit.clone());
+				}
+			}
+		}
+		credits
+	}
+	pub fn lyric_context(&self, all_albums: &[Album]) -> LyricContext {
+		LyricContext {
+			artist: self.artist.clone(),
+			title: self.title.clone(),
+			album: match self.parent_album_indices {
+				Some((album_index, _)) => all_albums[album_index].title.clone(),
+				None => self.title.clone()
+			},
+			duration: self.duration
+		}
+	}
 	pub fn destination_location(&self, codec: &AudioCodec) -> std::path::PathBuf {
 		globals::filezone()
 			.join(if self.bonus {
@@ -411,7 +541,8 @@ impl Song {
 			AudioCodec::Mp3 => {
 				let mut tag = id3::Tag::new();
 				id3::TagLike::set_title(&mut tag, &self.title);
-				id3::TagLike::set_artist(&mut tag, &self.artist);
+				// id3v2.4 separates multiple values with nulls
+				id3::TagLike::set_artist(&mut tag, self.artists.join("\0"));
 				match self.parent_album_indices {
 					Some((album_index, song_index)) => {
 						let parent_album = &all_albums[album_index];
@@ -446,14 +577,16 @@ impl Song {
 					id3::Frame::text("TCON", self.genre.to_string())
 				);
 				id3::TagLike::set_year(&mut tag, i32::from(self.released.year));
+				// id3 timestamps are in utc, so midnight somewhere east of here is the day before
+				let (utc_date, utc_hour, utc_minute) = self.release_time.to_utc(&self.released);
 				id3::TagLike::set_date_released(
 					&mut tag,
 					id3::Timestamp {
-						year: i32::from(self.released.year),
-						month: Some(self.released.month),
-						day: Some(self.released.day),
-						hour: None,
-						minute: None,
+						year: i32::from(utc_date.year),
+						month: Some(utc_date.month),
+						day: Some(utc_date.day),
+						hour: Some(utc_hour),
+						minute: Some(utc_minute),
 						second: None
 					}
 				);
@@ -479,6 +612,62 @@ impl Song {
 						id3::frame::Frame::link("WOAS", woas_string)
 					);
 				}
+				let _ = id3::TagLike::add_frame(
+					&mut tag,
+					id3::frame::ExtendedText {
+						description: String::from("ITUNESADVISORY"),
+						value: self.explicit.itunes_code().to_string()
+					}
+				);
+				let _ = id3::TagLike::add_frame(
+					&mut tag,
+					id3::frame::ExtendedText {
+						description: String::from("RELEASETYPE"),
+						value: self.release_type.to_string().to_string()
+					}
+				);
+				if let Some((_, kind)) = &self.version_of {
+					let _ = id3::TagLike::add_frame(
+						&mut tag,
+						id3::frame::ExtendedText {
+							description: String::from("VERSION"),
+							value: kind.label().to_string()
+						}
+					);
+				}
+				let chapters = self.chapters();
+				if !chapters.is_empty() {
+					let element_ids: Vec<String> = (0..chapters.len())
+						.map(|index| format!("chp{}", index))
+						.collect();
+					for ((start, end, sample), element_id) in chapters.iter().zip(&element_ids) {
+						let _ = id3::TagLike::add_frame(
+							&mut tag,
+							id3::frame::Chapter {
+								element_id: element_id.clone(),
+								start_time: start.milliseconds(),
+								end_time: end.milliseconds(),
+								// all ones means go by the times instead
+								start_offset: u32::MAX,
+								end_offset: u32::MAX,
+								frames: vec![id3::frame::Frame::text(
+									"TIT2",
+									sample.format_title()
+								)]
+							}
+						);
+					}
+					let _ = id3::TagLike::add_frame(
+						&mut tag,
+						id3::frame::TableOfContents {
+							element_id: String::from("toc"),
+							top_level: true,
+							ordered: true,
+							elements: element_ids,
+							frames: Vec::new()
+						}
+					);
+				}
 				let _ =
 					id3::TagLike::add_frame(&mut tag, id3::frame::Frame::text("TENC", "distri"));
 				let _ = id3::TagLike::add_frame(&mut tag, id3::frame::Frame::text("TFLT", "mp3"));
@@ -496,7 +685,8 @@ impl Song {
 					let uslt = id3::frame::Lyrics {
 						lang: lang_code.clone(),
 						description: String::new(),
-						text: lyrics.as_filetype(lyric::TextCodec::Txt)
+						text: lyrics
+							.as_filetype(lyric::TextCodec::Txt, &self.lyric_context(all_albums))
 					};
 					let _ = id3::TagLike::add_frame(&mut tag, uslt);
 					let sylt = id3::frame::Frame::with_content(
@@ -519,6 +709,59 @@ impl Song {
 						id3::frame::Frame::text("TLEN", self.duration.milliseconds().to_string())
 					);
 				}
+				let credits = self.all_credits(all_albums);
+				for (frame_id, role) in [
+					("TCOM", Role::Composer),
+					("TEXT", Role::Lyricist),
+					("TPE4", Role::Remixer)
+				] {
+					let names = credit::names_for(&credits, role);
+					if !names.is_empty() {
+						let _ = id3::TagLike::add_frame(
+							&mut tag,
+							id3::frame::Frame::text(frame_id, names.join("\0"))
+						);
+					}
+				}
+				// TIPL is for people behind the scenes, TMCL for people you can hear
+				let involved = |role: Role, involvement: &str| {
+					credit::names_for(&credits, role)
+						.into_iter()
+						.map(|name| id3::frame::InvolvedPeopleListItem {
+							involvement: involvement.to_string(),
+							involvee: name.to_string()
+						})
+						.collect::<Vec<_>>()
+				};
+				let tipl_items: Vec<id3::frame::InvolvedPeopleListItem> = [
+					involved(Role::Producer, "producer"),
+					involved(Role::Mixer, "mix"),
+					involved(Role::Engineer, "engineer")
+				]
+				.concat();
+				let mut tmcl_items: Vec<id3::frame::InvolvedPeopleListItem> = self
+					.lyrics
+					.iter()
+					.flat_map(|lyrics| lyrics.credits())
+					.map(|vocalist| id3::frame::InvolvedPeopleListItem {
+						involvement: String::from("vocals"),
+						involvee: vocalist.name.clone()
+					})
+					.collect();
+				tmcl_items.extend(involved(Role::Featured, "featured"));
+				for (frame_id, items) in [("TIPL", tipl_items), ("TMCL", tmcl_items)] {
+					if !items.is_empty() {
+						let _ = id3::TagLike::add_frame(
+							&mut tag,
+							id3::frame::Frame::with_content(
+								frame_id,
+								id3::frame::Content::InvolvedPeopleList(
+									id3::frame::InvolvedPeopleList { items }
+								)
+							)
+						);
+					}
+				}
 				if tag
 					.write_to_path(&temporary_destination, id3::Version::Id3v24)
 					.is_err()
@@ -536,6 +779,7 @@ impl Song {
 					});
 				tag.set_vorbis("TITLE", vec![&self.title]);
 				tag.set_vorbis("ARTIST", vec![&self.artist]);
+				tag.set_vorbis("ARTISTS", self.artists.clone());
 				match self.parent_album_indices {
 					Some((album_index, song_index)) => {
 						let parent_album = &all_albums[album_index];
@@ -580,6 +824,16 @@ impl Song {
 					tag.set_vorbis("WOAS", vec![woas_string]);
 				}
 				tag.set_vorbis("GENRE", vec![self.genre.to_string()]);
+				tag.set_vorbis("ITUNESADVISORY", vec![self.explicit.itunes_code()]);
+				tag.set_vorbis("RELEASETYPE", vec![self.release_type.to_string()]);
+				if let Some((_, kind)) = &self.version_of {
+					tag.set_vorbis("VERSION", vec![kind.label()]);
+				}
+				for (index, (start, _, sample)) in self.chapters().iter().enumerate() {
+					let key = format!("CHAPTER{:03}", index + 1);
+					tag.set_vorbis(key.clone(), vec![start.chapter_timestamp()]);
+					tag.set_vorbis(format!("{}NAME", key), vec![sample.format_title()]);
+				}
 				tag.set_vorbis("ENCODER", vec!["distri"]);
 				tag.set_vorbis("FILETYPE", vec!["flac"]);
 				if let Some((album_index, _)) = self.parent_album_indices {
@@ -592,12 +846,44 @@ impl Song {
 					);
 				}
 				if let Some(lyrics) = &self.lyrics {
-					tag.set_vorbis("LYRICS", vec![&lyrics.as_filetype(lyric::TextCodec::Txt)]);
+					let lyric_context = self.lyric_context(all_albums);
+					tag.set_vorbis(
+						"LYRICS",
+						vec![&lyrics.as_filetype(lyric::TextCodec::Txt, &lyric_context)]
+					);
 					tag.set_vorbis(
 						"LYRICS_SYNCED",
-						vec![&lyrics.as_filetype(lyric::TextCodec::Lrc)]
+						vec![&lyrics.as_filetype(lyric::TextCodec::Lrc, &lyric_context)]
 					);
 				}
+				let credits = self.all_credits(all_albums);
+				for (key, role) in [
+					("COMPOSER", Role::Composer),
+					("LYRICIST", Role::Lyricist),
+					("PRODUCER", Role::Producer),
+					("REMIXER", Role::Remixer),
+					("MIXER", Role::Mixer),
+					("ENGINEER", Role::Engineer)
+				] {
+					let names = credit::names_for(&credits, role);
+					if !names.is_empty() {
+						tag.set_vorbis(key, names);
+					}
+				}
+				let performers: Vec<String> = self
+					.lyrics
+					.iter()
+					.flat_map(|lyrics| lyrics.credits())
+					.map(|vocalist| format!("{} (vocals)", vocalist.name))
+					.chain(
+						credit::names_for(&credits, Role::Featured)
+							.into_iter()
+							.map(|name| format!("{} (featured)", name))
+					)
+					.collect();
+				if !performers.is_empty() {
+					tag.set_vorbis("PERFORMER", performers);
+				}
 				assert!(
 					tag.save().is_ok(),
 					"Couldn't write flac metadata for {}",
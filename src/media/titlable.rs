@@ -1,6 +1,13 @@
 use crate::globals;
 use crate::media::{album::Album, artwork::Artwork, audiocodec::AudioCodec, song::Song};
-use crate::types::{color::Palette, date::Date, duration::Duration, genre::Genre};
+use crate::types::{
+	advisory::Advisory,
+	color::Palette,
+	date::{Date, ReleaseTime},
+	duration::Duration,
+	genre::Genre,
+	releasetype::ReleaseType
+};
 
 #[derive(Debug)]
 pub enum Titlable<'a> {
@@ -32,6 +39,30 @@ impl Titlable<'_> {
 			Titlable::Album(album) => &album.slug
 		}
 	}
+	pub fn previous_slugs(&self) -> &[String] {
+		match self {
+			Titlable::Song(song) => &song.previous_slugs,
+			Titlable::Album(album) => &album.previous_slugs
+		}
+	}
+	// songs that came out ahead of their album count as singles
+	pub fn release_type(&self) -> ReleaseType {
+		match self {
+			Titlable::Song(song)
+				if song.parent_album_indices.is_some() && song.released_as_single =>
+			{
+				ReleaseType::Single
+			}
+			Titlable::Song(song) => song.release_type,
+			Titlable::Album(album) => album.release_type
+		}
+	}
+	pub fn release_time(&self) -> &ReleaseTime {
+		match self {
+			Titlable::Song(song) => &song.release_time,
+			Titlable::Album(album) => &album.release_time
+		}
+	}
 	pub fn released(&self) -> &Date {
 		match self {
 			Titlable::Song(song) => &song.released,
@@ -56,6 +87,12 @@ impl Titlable<'_> {
 			Titlable::Album(album) => album.unreleased
 		}
 	}
+	pub fn advisory(&self) -> Advisory {
+		match self {
+			Titlable::Song(song) => song.explicit,
+			Titlable::Album(album) => album.advisory()
+		}
+	}
 	pub fn artwork(&self) -> Option<&Artwork> {
 		match self {
 			Titlable::Song(song) => song.artwork.as_ref(),
@@ -71,7 +108,7 @@ impl Titlable<'_> {
 	fn dash(&self) -> &'static str {
 		match self {
 			Titlable::Song(song) => {
-				if song.event {
+				if song.release_type == ReleaseType::DjMix {
 					"@"
 				} else {
 					"–" // en dash btw
@@ -81,10 +118,14 @@ impl Titlable<'_> {
 		}
 	}
 	pub fn audio_download_url(&self, codec: &AudioCodec) -> String {
+		self.audio_download_url_for_slug(codec, self.slug())
+	}
+	// where the audio used to live back when this had some other slug
+	pub fn audio_download_url_for_slug(&self, codec: &AudioCodec, slug: &str) -> String {
 		format!(
 			"https://audio.astronomy487.com/{}/{}.{}",
 			codec.ext(),
-			self.public_filename(),
+			public_filename_for_slug(slug),
 			match self {
 				Titlable::Song(_) => codec.ext(),
 				Titlable::Album(_) => "zip"
@@ -116,7 +157,9 @@ impl Titlable<'_> {
 	pub fn format_title(&self) -> String {
 		match self {
 			Titlable::Song(song)
-				if song.parent_album_indices.is_none() && song.artist == "Astro" && !song.event =>
+				if song.parent_album_indices.is_none()
+					&& song.artist == "Astro"
+					&& song.release_type != ReleaseType::DjMix =>
 			{
 				self.title().to_string()
 			}
@@ -140,22 +183,13 @@ impl Titlable<'_> {
 			cleaned.push('_');
 		}
 		cleaned */
-		let mut cleaned = self.slug().to_string();
-		const RESERVED: &[&str] = &[
-			"CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7",
-			"COM8", "COM9", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
-			"CONIN$", "CONOUT$"
-		];
-		while RESERVED.contains(&cleaned.to_ascii_uppercase().as_str()) {
-			cleaned.push('_');
-		}
-		cleaned
+		public_filename_for_slug(self.slug())
 	}
 	pub fn format_title_short(&self) -> String {
 		if self.artist() == "Astro"
 			&& match self {
 				Titlable::Album(_) => true,
-				Titlable::Song(song) => !song.event
+				Titlable::Song(song) => song.release_type != ReleaseType::DjMix
 			} {
 			self.title().to_string()
 		} else {
@@ -167,32 +201,40 @@ impl Titlable<'_> {
 			Titlable::Album(album) => {
 				let released = album.released.to_display();
 				let track_count = album.songs.iter().filter(|s| !s.bonus).count();
-				format!(
-					"Album released {}, {} tracks, {}",
-					released,
-					track_count,
-					album.duration.display()
-				)
+				if album.is_single() {
+					format!("Single released {}, {}", released, album.duration.display())
+				} else {
+					format!(
+						"{} released {}, {} tracks, {}",
+						album.release_type.label(),
+						released,
+						track_count,
+						album.duration.display()
+					)
+				}
 			}
 			Titlable::Song(song) => {
 				let released = song.released.to_display();
 				match song.parent_album_indices {
-					None => {
-						if song.event {
-							format!(
-								"DJ set for {} on {}, {}",
-								song.title,
-								released,
-								song.duration.display()
-							)
-						} else if !song.artist.is_empty() {
-							format!("Remix released {}, {}", released, song.duration.display())
-						} else {
+					None => match song.release_type {
+						ReleaseType::DjMix => format!(
+							"DJ set for {} on {}, {}",
+							song.title,
+							released,
+							song.duration.display()
+						),
+						ReleaseType::Mixtape => {
 							format!("Mix released {}, {}", released, song.duration.display())
 						}
-					}
+						ReleaseType::Live => format!(
+							"Live recording released {}, {}",
+							released,
+							song.duration.display()
+						),
+						_ => format!("Remix released {}, {}", released, song.duration.display())
+					},
 					Some((parent_album_index, track_number)) => {
-						if all_albums[parent_album_index].single {
+						if all_albums[parent_album_index].is_single() {
 							format!("Song released {}, {}", released, song.duration.display())
 						} else {
 							format!(
@@ -209,3 +251,16 @@ impl Titlable<'_> {
 		}
 	}
 }
+
+fn public_filename_for_slug(slug: &str) -> String {
+	let mut cleaned = slug.to_string();
+	const RESERVED: &[&str] = &[
+		"CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8",
+		"COM9", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9", "CONIN$",
+		"CONOUT$"
+	];
+	while RESERVED.contains(&cleaned.to_ascii_uppercase().as_str()) {
+		cleaned.push('_');
+	}
+	cleaned
+}
@@ -0,0 +1,112 @@
+use crate::globals;
+use crate::media::schema;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Vocalist {
+	pub name: String,
+	pub aliases: Vec<String>,
+	pub url: Option<String>
+}
+
+impl Vocalist {
+	fn from_json(val: &serde_json::Value) -> Vocalist {
+		let obj =
+			globals::map_with_only_these_keys(val, "Vocalist", &schema::keys(schema::VOCALIST));
+		let name = obj
+			.get("name")
+			.unwrap_or_else(|| panic!("Vocalist {} has no name", val))
+			.as_str()
+			.unwrap_or_else(|| panic!("Vocalist {} name is not a string", val))
+			.to_string();
+		validate_name(&name);
+		let aliases: Vec<String> = match obj.get("aliases") {
+			None => Vec::new(),
+			Some(aliases_json) => aliases_json
+				.as_array()
+				.unwrap_or_else(|| panic!("Vocalist {} aliases must be an array", name))
+				.iter()
+				.map(|alias_json| {
+					let alias = alias_json
+						.as_str()
+						.unwrap_or_else(|| panic!("Vocalist {} has a non-string alias", name))
+						.to_string();
+					validate_name(&alias);
+					alias
+				})
+				.collect()
+		};
+		let url = obj.get("url").map(|url_json| {
+			let url = url_json
+				.as_str()
+				.unwrap_or_else(|| panic!("Vocalist {} url is not a string", name));
+			assert!(
+				url.starts_with("https://"),
+				"Vocalist {} url \"{}\" must start with https://",
+				name,
+				url
+			);
+			url.to_string()
+		});
+		Vocalist { name, aliases, url }
+	}
+	fn answers_to(&self, name: &str) -> bool {
+		self.name.to_lowercase() == name.to_lowercase()
+			|| self
+				.aliases
+				.iter()
+				.any(|alias| alias.to_lowercase() == name.to_lowercase())
+	}
+}
+
+fn validate_name(name: &str) {
+	assert!(
+		!name.is_empty()
+			&& !name.starts_with(char::is_whitespace)
+			&& !name.ends_with(char::is_whitespace),
+		"Vocalist name \"{}\" is empty or has untrimmed whitespace",
+		name
+	);
+	assert!(
+		!name.contains(',') && !name.contains('\t'),
+		"Vocalist name \"{}\" must not contain commas or tabs",
+		name
+	);
+	assert!(
+		name.to_lowercase() != "unknown",
+		"\"{}\" is not allowed as a vocalist; figure out who it is",
+		name
+	);
+}
+
+// every person who's allowed to show up in a vocalist: tag
+#[derive(Default)]
+pub struct VocalistRegistry {
+	vocalists: Vec<Vocalist>
+}
+
+impl VocalistRegistry {
+	pub fn from_json(val: &serde_json::Value) -> VocalistRegistry {
+		let vocalists: Vec<Vocalist> = val
+			.as_array()
+			.expect("discog.json \"vocalists\" attribute is not an array")
+			.iter()
+			.map(Vocalist::from_json)
+			.collect();
+		let mut seen_names = std::collections::HashSet::new();
+		for vocalist in &vocalists {
+			for name in std::iter::once(&vocalist.name).chain(&vocalist.aliases) {
+				assert!(
+					seen_names.insert(name.to_lowercase()),
+					"Vocalist name or alias \"{}\" is used more than once",
+					name
+				);
+			}
+		}
+		VocalistRegistry { vocalists }
+	}
+	pub fn resolve(&self, name: &str) -> Option<&Vocalist> {
+		self.vocalists
+			.iter()
+			.find(|vocalist| vocalist.answers_to(name))
+	}
+}
@@ -0,0 +1,43 @@
+// parental advisory, the little "E" next to songs in every store
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Advisory {
+	NotExplicit,
+	Explicit,
+	Clean // edited version of something explicit
+}
+
+impl Advisory {
+	pub fn from_json(val: &serde_json::Value) -> Advisory {
+		match val {
+			serde_json::Value::Bool(true) => Advisory::Explicit,
+			serde_json::Value::Bool(false) => Advisory::NotExplicit,
+			serde_json::Value::String(string) if string == "clean" => Advisory::Clean,
+			_ => panic!(
+				"\"explicit\" must be true, false, or \"clean\", not {}",
+				val
+			)
+		}
+	}
+	// an album is explicit if anything on it is
+	pub fn of_all(advisories: impl Iterator<Item = Advisory>) -> Advisory {
+		advisories.fold(Advisory::NotExplicit, |so_far, advisory| {
+			match (so_far, advisory) {
+				(Advisory::Explicit, _) | (_, Advisory::Explicit) => Advisory::Explicit,
+				(Advisory::Clean, _) | (_, Advisory::Clean) => Advisory::Clean,
+				_ => Advisory::NotExplicit
+			}
+		})
+	}
+	pub fn is_explicit(&self) -> bool {
+		*self == Advisory::Explicit
+	}
+	// ITUNESADVISORY in vorbis comments and TXXX:ITUNESADVISORY in id3
+	pub fn itunes_code(&self) -> &'static str {
+		match self {
+			Advisory::NotExplicit => "0",
+			Advisory::Explicit => "1",
+			Advisory::Clean => "2"
+		}
+	}
+}
@@ -0,0 +1,39 @@
+macro_rules! define_assist_roles {
+	(
+		$(
+			$text:expr, $name:ident ;
+		)*
+	) => {
+		#[derive(Clone, Debug)]
+		pub enum AssistRole {
+			$($name),*
+		}
+		impl AssistRole {
+			pub const ALL: &[AssistRole] = &[$(AssistRole::$name),*];
+			pub fn to_string(&self) -> &'static str {
+				match self {
+					$(AssistRole::$name => $text),*
+				}
+			}
+			pub fn from(s: &str) -> AssistRole {
+				match s {
+					$(
+						$text => AssistRole::$name,
+					)*
+					_ => panic!("Unrecognized assist role \"{}\"", s),
+				}
+			}
+		}
+	};
+}
+
+define_assist_roles!(
+	"Producer", Producer;
+	"Additional producer", AdditionalProducer;
+	"Additional writer", AdditionalWriter;
+	"Additional writer and producer", AdditionalWriterAndProducer;
+	"Contributing artist", ContributingArtist;
+	"Remixer", Remixer;
+	"Keys", Keys;
+	"Synthesizer solo", SynthesizerSolo;
+);
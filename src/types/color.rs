@@ -1,4 +1,5 @@
 use crate::globals;
+use crate::media::schema;
 use crate::types::urlset::UrlSet;
 
 #[derive(Clone, Debug)]
@@ -75,6 +76,73 @@ impl Color {
 		}
 		self.lerp(high, other)
 	}
+	fn to_hsl(&self) -> (f32, f32, f32) {
+		let red = f32::from(self.0) / 255.0;
+		let green = f32::from(self.1) / 255.0;
+		let blue = f32::from(self.2) / 255.0;
+		let max = red.max(green).max(blue);
+		let min = red.min(green).min(blue);
+		let lightness = f32::midpoint(max, min);
+		let delta = max - min;
+		if delta <= 0.0 {
+			return (0.0, 0.0, lightness);
+		}
+		let saturation = if lightness > 0.5 {
+			delta / (2.0 - max - min)
+		} else {
+			delta / (max + min)
+		};
+		let hue = 60.0
+			* if max == red {
+				((green - blue) / delta).rem_euclid(6.0)
+			} else if max == green {
+				(blue - red) / delta + 2.0
+			} else {
+				(red - green) / delta + 4.0
+			};
+		(hue, saturation, lightness)
+	}
+	fn from_hsl(hue: f32, saturation: f32, lightness: f32) -> Color {
+		fn channel(value: f32) -> u8 {
+			(value * 255.0).round().clamp(0.0, 255.0) as u8
+		}
+		let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+		let sector = hue / 60.0;
+		let second = chroma * (1.0 - (sector.rem_euclid(2.0) - 1.0).abs());
+		let (red, green, blue) = match sector as u32 {
+			0 => (chroma, second, 0.0),
+			1 => (second, chroma, 0.0),
+			2 => (0.0, chroma, second),
+			3 => (0.0, second, chroma),
+			4 => (second, 0.0, chroma),
+			_ => (chroma, 0.0, second)
+		};
+		let offset = lightness - chroma / 2.0;
+		Color(
+			channel(red + offset),
+			channel(green + offset),
+			channel(blue + offset)
+		)
+	}
+	// nearest color with the same hue and saturation (only lightness moves) that satisfies the predicate
+	fn nearest_satisfying(&self, predicate: impl Fn(&Color) -> bool) -> Option<Color> {
+		let (hue, saturation, lightness) = self.to_hsl();
+		for step in 1..=200_u8 {
+			let offset = f32::from(step) * 0.005;
+			for candidate_lightness in [lightness - offset, lightness + offset] {
+				if (0.0..=1.0).contains(&candidate_lightness) {
+					let candidate = Color::from_hsl(hue, saturation, candidate_lightness);
+					if predicate(&candidate) {
+						return Some(candidate);
+					}
+				}
+			}
+		}
+		None
+	}
+	pub fn nearest_with_contrast(&self, other: &Color, required_contrast: f32) -> Option<Color> {
+		self.nearest_satisfying(|candidate| candidate.contrast(other) >= required_contrast)
+	}
 	pub const CYAN: Color = Color(0, 255, 255);
 	pub const MAGENTA: Color = Color(255, 0, 255);
 	pub const YELLOW: Color = Color(255, 255, 0);
@@ -86,6 +154,7 @@ impl std::fmt::Display for Color {
 		write!(fmt, "#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
 	}
 }
+pub const PALETTE_MODES: &[&str] = &["black", "white"]; // what "mode" can say; Normal is just leaving it out
 #[derive(PartialEq, Clone, Debug)]
 pub enum PaletteMode {
 	Normal,
@@ -125,11 +194,7 @@ impl Palette {
 		}
 	}
 	pub fn from(val: &serde_json::Value, url_set: &UrlSet) -> Palette {
-		let obj = globals::map_with_only_these_keys(
-			val,
-			"Color",
-			&["foreground", "background", "accent", "mode"]
-		);
+		let obj = globals::map_with_only_these_keys(val, "Color", &schema::keys(schema::COLOR));
 		let fg_val = obj.get("foreground").unwrap_or_else(|| {
 			panic!(
 				"\"color\" from JSON has no \"foreground\" attribute: {}",
@@ -185,17 +250,30 @@ impl Palette {
 		let foreground = Color::from(fg_str);
 		let background = Color::from(bg_str);
 		let accent = Color::from(acc_str);
+		fn suggestion(maybe_color: Option<Color>, what: &str) -> String {
+			match maybe_color {
+				Some(color) => format!("nearest passing {} is {}", what, color),
+				None => format!("no {} with the same hue passes", what)
+			}
+		}
 		assert!(
 			foreground.contrast(&background) >= 4.5,
-			"Foreground color {} has insufficient contrast with background color {}",
+			"Foreground color {} has insufficient contrast with background color {} ({:.2} < 4.5); {}",
 			foreground,
-			background
+			background,
+			foreground.contrast(&background),
+			suggestion(
+				foreground.nearest_with_contrast(&background, 4.5),
+				"foreground"
+			)
 		);
 		assert!(
 			accent.contrast(&background) >= 3.0,
-			"Accent color {} has insufficient contrast with background color {}",
+			"Accent color {} has insufficient contrast with background color {} ({:.2} < 3); {}",
 			accent,
-			background
+			background,
+			accent.contrast(&background),
+			suggestion(accent.nearest_with_contrast(&background, 3.0), "accent")
 		);
 		let palette = Palette {
 			gray: background.find_min_towards(&foreground, 4.5),
@@ -206,22 +284,54 @@ impl Palette {
 			palette_mode
 		};
 
+		let logo_colors = url_set.logo_colors_used();
 		let mut logo_colors_that_dont_pass: Vec<&'static str> = Vec::new();
-		for (platform, the_color) in url_set.logo_colors_used() {
+		for (platform, the_color) in &logo_colors {
 			let contrast = the_color.contrast(&palette.background);
-			if contrast < 3.0 {
+			if contrast < 3.0 && !logo_colors_that_dont_pass.contains(platform) {
 				logo_colors_that_dont_pass.push(platform);
 			}
 		}
 		if logo_colors_that_dont_pass.is_empty() && palette.palette_mode != PaletteMode::Normal {
-			// panic!("Your palette ({}, {}, {}) could use normal mode but does not", palette.background, palette.accent, palette.foreground);
+			globals::log_2(
+				"Warning",
+				format!(
+					"Palette ({}, {}, {}) could use normal mode instead of {} mode",
+					palette.background,
+					palette.accent,
+					palette.foreground,
+					match palette.palette_mode {
+						PaletteMode::White => "white",
+						_ => "black"
+					}
+				),
+				globals::ANSI_RED
+			);
+		}
+		if !logo_colors_that_dont_pass.is_empty() && palette.palette_mode == PaletteMode::Normal {
+			let nearest_background = palette.background.nearest_satisfying(|candidate| {
+				logo_colors
+					.iter()
+					.all(|(_, logo_color)| logo_color.contrast(candidate) >= 3.0)
+					&& palette.foreground.contrast(candidate) >= 4.5
+					&& palette.accent.contrast(candidate) >= 3.0
+			});
+			let would_fix = |mode_color: &Color| {
+				if mode_color.contrast(&palette.background) >= 3.0 {
+					"would fix this"
+				} else {
+					"would not fix this"
+				}
+			};
+			panic!(
+				"Background color {} has insufficient contrast for {}; {}; \"mode\": \"white\" {}; \"mode\": \"black\" {}",
+				palette.background,
+				logo_colors_that_dont_pass.join(", "),
+				suggestion(nearest_background, "background"),
+				would_fix(&Color::WHITE),
+				would_fix(&Color::BLACK)
+			);
 		}
-		assert!(
-			logo_colors_that_dont_pass.is_empty() || palette.palette_mode != PaletteMode::Normal,
-			"Background color {} has insufficient contrast for {}",
-			palette.background,
-			logo_colors_that_dont_pass.join(", ")
-		);
 
 		match &palette.palette_mode {
 			PaletteMode::Normal => {}
@@ -243,3 +353,15 @@ impl Palette {
 		palette
 	}
 }
+
+#[test]
+fn nearest_color_passes_and_keeps_hue() {
+	let background = Color::from("#08083e");
+	let accent = Color::from("#3a3aa0");
+	assert!(accent.contrast(&background) < 3.0);
+	let fixed = accent
+		.nearest_with_contrast(&background, 3.0)
+		.expect("Some lighter blue should pass");
+	assert!(fixed.contrast(&background) >= 3.0);
+	assert!((fixed.to_hsl().0 - accent.to_hsl().0).abs() < 2.0);
+}
@@ -61,27 +61,21 @@ impl Date {
 			_ => unreachable!()
 		}
 	}
-	pub fn to_rfc822(&self) -> String {
+	pub fn to_rfc822(&self, time: &ReleaseTime) -> String {
 		format!(
-			"{}, {:02} {} {} 17:00:00 GMT",
+			"{}, {:02} {} {} {:02}:{:02}:00 {}",
 			self.weekday_name(),
 			self.day,
-			match self.month {
-				1 => "Jan",
-				2 => "Feb",
-				3 => "Mar",
-				4 => "Apr",
-				5 => "May",
-				6 => "Jun",
-				7 => "Jul",
-				8 => "Aug",
-				9 => "Sep",
-				10 => "Oct",
-				11 => "Nov",
-				12 => "Dec",
-				_ => unreachable!()
-			},
-			self.year
+			self.month_name(),
+			self.year,
+			time.hour,
+			time.minute,
+			// utc stays "GMT" like it always was, so old feed entries don't change
+			if time.offset_minutes == 0 {
+				String::from("GMT")
+			} else {
+				time.offset_text().replace(':', "")
+			}
 		)
 	}
 	pub fn today() -> Date {
@@ -89,8 +83,10 @@ impl Date {
 			.duration_since(std::time::UNIX_EPOCH)
 			.unwrap_or_else(|_| std::time::Duration::from_secs(0))
 			.as_secs();
-		let days = now / 86400;
-		let z_value = days as i64 + 719_468;
+		Date::from_days_since_epoch((now / 86400) as i64)
+	}
+	fn from_days_since_epoch(days: i64) -> Date {
+		let z_value = days + 719_468;
 		let era = (if z_value >= 0 {
 			z_value
 		} else {
@@ -104,9 +100,36 @@ impl Date {
 		let month = mp + if mp < 10 { 3 } else { -9 };
 		let year = yoe + era * 400 + i64::from(month <= 2);
 		Date {
-			year: year.try_into().expect("Could not calculate today's date"),
-			month: month.try_into().expect("Could not calculate today's date"),
-			day: day.try_into().expect("Could not calculate today's date")
+			year: year.try_into().expect("Could not calculate date"),
+			month: month.try_into().expect("Could not calculate date"),
+			day: day.try_into().expect("Could not calculate date")
+		}
+	}
+	// the inverse of from_days_since_epoch
+	fn days_since_epoch(&self) -> i64 {
+		let year = i64::from(self.year) - i64::from(self.month <= 2);
+		let era = (if year >= 0 { year } else { year - 399 }) / 400;
+		let yoe = year - era * 400;
+		let mp = (i64::from(self.month) + 9) % 12;
+		let doy = (153 * mp + 2) / 5 + i64::from(self.day) - 1;
+		let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+		era * 146_097 + doe - 719_468
+	}
+	fn month_name(&self) -> &'static str {
+		match self.month {
+			1 => "Jan",
+			2 => "Feb",
+			3 => "Mar",
+			4 => "Apr",
+			5 => "May",
+			6 => "Jun",
+			7 => "Jul",
+			8 => "Aug",
+			9 => "Sep",
+			10 => "Oct",
+			11 => "Nov",
+			12 => "Dec",
+			_ => unreachable!()
 		}
 	}
 
@@ -131,21 +154,7 @@ impl Date {
 			_ => unreachable!()
 		};
 		let today = Date::today();
-		let month_name = match today.month {
-			1 => "Jan",
-			2 => "Feb",
-			3 => "Mar",
-			4 => "Apr",
-			5 => "May",
-			6 => "Jun",
-			7 => "Jul",
-			8 => "Aug",
-			9 => "Sep",
-			10 => "Oct",
-			11 => "Nov",
-			12 => "Dec",
-			_ => unreachable!()
-		};
+		let month_name = today.month_name();
 		format!(
 			"{}, {:02} {} {} {:02}:{:02}:{:02} GMT",
 			weekday_name, today.day, month_name, today.year, hours, mins, secs
@@ -155,28 +164,159 @@ impl Date {
 		format!("{:04}-{:02}-{:02}", self.year, self.month, self.day)
 	}
 	pub fn to_display(&self) -> String {
-		format!(
-			"{} {} {}",
-			match self.month {
-				1 => "Jan",
-				2 => "Feb",
-				3 => "Mar",
-				4 => "Apr",
-				5 => "May",
-				6 => "Jun",
-				7 => "Jul",
-				8 => "Aug",
-				9 => "Sep",
-				10 => "Oct",
-				11 => "Nov",
-				12 => "Dec",
-				_ => unreachable!()
-			},
-			self.day,
-			self.year
-		)
+		format!("{} {} {}", self.month_name(), self.day, self.year)
 	}
 	pub fn birthday(&self) -> bool {
 		self.month == 12 && self.day == 5
 	}
 }
+
+// when on its release date something comes out, and in what utc offset
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReleaseTime {
+	pub hour: u8,
+	pub minute: u8,
+	pub offset_minutes: i16 // east of utc, like +09:00 is 540
+}
+
+impl ReleaseTime {
+	// "release_time" is HH:MM, "timezone" is an offset like "+09:00", "-05:00" or "Z"
+	// either one falls back to the parent's, then to the defaults in globals.rs
+	pub fn from_json(
+		obj: &serde_json::Map<String, serde_json::Value>, parent: Option<&ReleaseTime>, what: &str
+	) -> ReleaseTime {
+		let text_of = |key: &str| {
+			obj.get(key).map(|val| {
+				val.as_str().unwrap_or_else(|| {
+					panic!(
+						"{} JSON attribute \"{}\" is not a string: {}",
+						what, key, val
+					)
+				})
+			})
+		};
+		let (hour, minute) = match text_of("release_time") {
+			Some(text) => ReleaseTime::parse_clock(text),
+			None => match parent {
+				Some(parent_time) => (parent_time.hour, parent_time.minute),
+				None => ReleaseTime::parse_clock(crate::globals::DEFAULT_RELEASE_TIME)
+			}
+		};
+		let offset_minutes = match text_of("timezone") {
+			Some(text) => ReleaseTime::parse_offset(text),
+			None => match parent {
+				Some(parent_time) => parent_time.offset_minutes,
+				None => ReleaseTime::parse_offset(crate::globals::DEFAULT_TIMEZONE)
+			}
+		};
+		ReleaseTime {
+			hour,
+			minute,
+			offset_minutes
+		}
+	}
+	pub fn default() -> ReleaseTime {
+		let (hour, minute) = ReleaseTime::parse_clock(crate::globals::DEFAULT_RELEASE_TIME);
+		ReleaseTime {
+			hour,
+			minute,
+			offset_minutes: ReleaseTime::parse_offset(crate::globals::DEFAULT_TIMEZONE)
+		}
+	}
+	fn parse_clock(hh_mm: &str) -> (u8, u8) {
+		let bad = || -> ! {
+			panic!("Release time must be in HH:MM format: \"{}\"", hh_mm);
+		};
+		if hh_mm.len() != 5 || &hh_mm[2..3] != ":" {
+			bad();
+		}
+		let hour: u8 = hh_mm[0..2].parse().unwrap_or_else(|_| bad());
+		let minute: u8 = hh_mm[3..5].parse().unwrap_or_else(|_| bad());
+		if hour > 23 || minute > 59 {
+			bad();
+		}
+		(hour, minute)
+	}
+	fn parse_offset(offset: &str) -> i16 {
+		if offset == "Z" {
+			return 0;
+		}
+		let bad = || -> ! {
+			panic!(
+				"Timezone must be a utc offset like \"+09:00\" or \"-05:00\": \"{}\"",
+				offset
+			);
+		};
+		let sign = match offset.get(0..1) {
+			Some("+") => 1,
+			Some("-") => -1,
+			_ => bad()
+		};
+		let Some(clock) = offset.get(1..) else {
+			bad();
+		};
+		let (hours, minutes) = ReleaseTime::parse_clock(clock);
+		let offset_minutes = sign * (i16::from(hours) * 60 + i16::from(minutes));
+		if !(-12 * 60..=14 * 60).contains(&offset_minutes) {
+			bad();
+		}
+		offset_minutes
+	}
+	pub fn offset_text(&self) -> String {
+		format!(
+			"{}{:02}:{:02}",
+			if self.offset_minutes < 0 { '-' } else { '+' },
+			self.offset_minutes.abs() / 60,
+			self.offset_minutes.abs() % 60
+		)
+	}
+	// seconds since the unix epoch at which this goes out on that date
+	fn unix_seconds(&self, date: &Date) -> i64 {
+		date.days_since_epoch() * 86400 + i64::from(self.hour) * 3600 + i64::from(self.minute) * 60
+			- i64::from(self.offset_minutes) * 60
+	}
+	pub fn has_arrived(&self, date: &Date) -> bool {
+		let now = std::time::SystemTime::now()
+			.duration_since(std::time::UNIX_EPOCH)
+			.unwrap_or_else(|_| std::time::Duration::from_secs(0))
+			.as_secs();
+		i64::try_from(now).expect("It's too far in the future") >= self.unix_seconds(date)
+	}
+	// the same moment, in utc (what id3 timestamps want)
+	pub fn to_utc(&self, date: &Date) -> (Date, u8, u8) {
+		let seconds = self.unix_seconds(date);
+		let minutes_into_day = seconds.rem_euclid(86400) / 60;
+		(
+			Date::from_days_since_epoch(seconds.div_euclid(86400)),
+			(minutes_into_day / 60) as u8,
+			(minutes_into_day % 60) as u8
+		)
+	}
+	pub fn to_iso8601(&self, date: &Date) -> String {
+		format!(
+			"{}T{:02}:{:02}:00{}",
+			date.to_iso8601(),
+			self.hour,
+			self.minute,
+			self.offset_text()
+		)
+	}
+}
+
+#[test]
+fn release_times_know_their_timezone() {
+	let mut obj = serde_json::Map::new();
+	let _ = obj.insert("release_time".into(), "00:00".into());
+	let _ = obj.insert("timezone".into(), "+09:00".into());
+	let time = ReleaseTime::from_json(&obj, None, "Test");
+	let date = Date::from("2024-03-01");
+	// midnight in tokyo is still the day before in utc
+	assert_eq!(time.to_utc(&date), (Date::from("2024-02-29"), 15, 0));
+	assert_eq!(date.to_rfc822(&time), "Fri, 01 Mar 2024 00:00:00 +0900");
+	assert_eq!(time.to_iso8601(&date), "2024-03-01T00:00:00+09:00");
+	assert_eq!(Date::from_days_since_epoch(date.days_since_epoch()), date);
+	assert_eq!(
+		date.to_rfc822(&ReleaseTime::default()),
+		"Fri, 01 Mar 2024 17:00:00 GMT"
+	);
+}
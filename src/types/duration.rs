@@ -99,6 +99,59 @@ impl Duration {
 	pub fn milliseconds(&self) -> u32 {
 		self.milliseconds
 	}
+	// M:SS or H:MM:SS, like a tracklist would say
+	pub fn from_timestamp(text: &str) -> Option<Duration> {
+		let parts: Vec<&str> = text.split(':').collect();
+		if !(2..=3).contains(&parts.len())
+			|| parts
+				.iter()
+				.any(|part| part.is_empty() || !part.chars().all(|c| c.is_ascii_digit()))
+			|| parts[1..].iter().any(|part| part.len() != 2)
+		{
+			return None;
+		}
+		let mut seconds: u32 = 0;
+		for (index, part) in parts.iter().enumerate() {
+			let value: u32 = part.parse().ok()?;
+			if index > 0 && value >= 60 {
+				return None;
+			}
+			seconds = seconds * 60 + value;
+		}
+		Some(Self {
+			milliseconds: seconds * 1000
+		})
+	}
+	pub fn timestamp(&self) -> String {
+		let total_seconds = self.seconds();
+		let hours = total_seconds / 3600;
+		let minutes = (total_seconds / 60) % 60;
+		let seconds = total_seconds % 60;
+		if hours > 0 {
+			format!("{}:{:02}:{:02}", hours, minutes, seconds)
+		} else {
+			format!("{}:{:02}", minutes, seconds)
+		}
+	}
+	// MM:SS:FF with 75 frames a second, for cue sheet INDEX lines. minutes don't roll over into hours
+	pub fn cue_index(&self) -> String {
+		format!(
+			"{:02}:{:02}:{:02}",
+			self.milliseconds / 60000,
+			(self.milliseconds / 1000) % 60,
+			(self.milliseconds % 1000) * 75 / 1000
+		)
+	}
+	// HH:MM:SS.mmm, for vorbis CHAPTERxxx tags
+	pub fn chapter_timestamp(&self) -> String {
+		format!(
+			"{:02}:{:02}:{:02}.{:03}",
+			self.milliseconds / 3600000,
+			(self.milliseconds / 60000) % 60,
+			(self.milliseconds / 1000) % 60,
+			self.milliseconds % 1000
+		)
+	}
 	pub fn zero() -> Duration {
 		Self { milliseconds: 0 }
 	}
@@ -115,3 +168,22 @@ impl Duration {
 		}
 	}
 }
+
+#[test]
+fn timestamps_round_trip() {
+	for text in ["0:00", "3:07", "59:59", "1:02:03"] {
+		let parsed =
+			Duration::from_timestamp(text).unwrap_or_else(|| panic!("{} didn't parse", text));
+		assert_eq!(parsed.timestamp(), text);
+	}
+	assert_eq!(
+		Duration::from_timestamp("1:02:03").map(|at| at.seconds()),
+		Some(3723)
+	);
+	let at = Duration::from_milliseconds(3_723_500);
+	assert_eq!(at.cue_index(), "62:03:37");
+	assert_eq!(at.chapter_timestamp(), "01:02:03.500");
+	for text in ["", "3", "3:7", "3:60", "1:2:03", "-1:00", "1:00:00:00"] {
+		assert!(Duration::from_timestamp(text).is_none(), "{} parsed", text);
+	}
+}
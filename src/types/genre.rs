@@ -9,6 +9,7 @@ macro_rules! define_genres {
 			$($name),*
 		}
 		impl Genre {
+			pub const ALL: &[Genre] = &[$(Genre::$name),*];
 			pub fn to_string(&self) -> &'static str {
 				match self {
 					$(Genre::$name => $text),*
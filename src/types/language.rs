@@ -1,7 +1,9 @@
+use crate::types::script::Script;
+
 macro_rules! define_languages {
 	(
 		$(
-			$iso1:expr, $iso2:expr, $name:ident ;
+			$iso1:expr, $iso2:expr, $name:ident, [$($script:ident),*] ;
 		)*
 	) => {
 		#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
@@ -9,6 +11,7 @@ macro_rules! define_languages {
 			$($name),*
 		}
 		impl Language {
+			pub const ALL: &[Language] = &[$(Language::$name),*];
 			pub fn iso_639_1(&self) -> &'static str {
 				match self {
 					$(Language::$name => $iso1),*
@@ -19,6 +22,26 @@ macro_rules! define_languages {
 					$(Language::$name => $iso2),*
 				}
 			}
+			// scripts a line in this language is expected to be written in
+			pub fn scripts(&self) -> &'static [Script] {
+				match self {
+					$(Language::$name => &[$(Script::$script),*]),*
+				}
+			}
+			pub fn name(&self) -> String {
+				// "ScottishGaelic" -> "Scottish Gaelic"
+				let mut name = String::new();
+				let ident = match self {
+					$(Language::$name => stringify!($name)),*
+				};
+				for (index, character) in ident.chars().enumerate() {
+					if index > 0 && character.is_uppercase() {
+						name.push(' ');
+					}
+					name.push(character);
+				}
+				name
+			}
 			pub fn from(s: &str) -> Language {
 				#[allow(unreachable_patterns)]
 				match s {
@@ -34,189 +57,189 @@ macro_rules! define_languages {
 }
 
 define_languages! {
-	"aa", "aar", Afar;
-	"ab", "abk", Abkhazian;
-	"ae", "ave", Avestan;
-	"af", "afr", Afrikaans;
-	"ak", "aka", Akan;
-	"am", "amh", Amharic;
-	"an", "arg", Aragonese;
-	"ar", "ara", Arabic;
-	"as", "asm", Assamese;
-	"av", "ava", Avaric;
-	"ay", "aym", Aymara;
-	"az", "aze", Azerbaijani;
-	"ba", "bak", Bashkir;
-	"be", "bel", Belarusian;
-	"bg", "bul", Bulgarian;
-	"bi", "bis", Bislama;
-	"bm", "bam", Bambara;
-	"bn", "ben", Bengali;
-	"bo", "bod", Tibetan;
-	"br", "bre", Breton;
-	"bs", "bos", Bosnian;
-	"ca", "cat", Catalan;
-	"ce", "che", Chechen;
-	"ch", "cha", Chamorro;
-	"co", "cos", Corsican;
-	"cr", "cre", Cree;
-	"cs", "ces", Czech;
-	"cu", "chu", ChurchSlavonic;
-	"cv", "chv", Chuvash;
-	"cy", "cym", Welsh;
-	"da", "dan", Danish;
-	"de", "deu", German;
-	"dv", "div", Dhivehi;
-	"dz", "dzo", Dzongkha;
-	"ee", "ewe", Ewe;
-	"el", "ell", Greek;
-	"en", "eng", English;
-	"eo", "epo", Esperanto;
-	"es", "spa", Spanish;
-	"et", "est", Estonian;
-	"eu", "eus", Basque;
-	"fa", "fas", Persian;
-	"ff", "ful", Fulah;
-	"fi", "fin", Finnish;
-	"fj", "fij", Fijian;
-	"fo", "fao", Faroese;
-	"fr", "fra", French;
-	"fy", "fry", Frisian;
-	"ga", "gle", Irish;
-	"gd", "gla", ScottishGaelic;
-	"gl", "glg", Galician;
-	"gn", "grn", Guarani;
-	"gu", "guj", Gujarati;
-	"gv", "glv", Manx;
-	"ha", "hau", Hausa;
-	"he", "heb", Hebrew;
-	"hi", "hin", Hindi;
-	"ho", "hmo", HiriMotu;
-	"hr", "hrv", Croatian;
-	"ht", "hat", HaitianCreole;
-	"hu", "hun", Hungarian;
-	"hy", "hye", Armenian;
-	"hz", "her", Herero;
-	"ia", "ina", Interlingua;
-	"id", "ind", Indonesian;
-	"ie", "ile", Interlingue;
-	"ig", "ibo", Igbo;
-	"ii", "iii", Yi;
-	"ik", "ipk", Inupiaq;
-	"io", "ido", Ido;
-	"is", "isl", Icelandic;
-	"it", "ita", Italian;
-	"iu", "iku", Inuktitut;
-	"ja", "jpn", Japanese;
-	"jbo", "jbo", Lojban; // technically invalid ISO 693-1 code
-	"jv", "jav", Javanese;
-	"ka", "kat", Georgian;
-	"kg", "kon", Kongo;
-	"ki", "kik", Kikuyu;
-	"kj", "kua", Kwanyama;
-	"kk", "kaz", Kazakh;
-	"kl", "kal", Kalaallisut;
-	"km", "khm", Khmer;
-	"kn", "kan", Kannada;
-	"ko", "kor", Korean;
-	"kr", "kau", Kanuri;
-	"ks", "kas", Kashmiri;
-	"ku", "kur", Kurdish;
-	"kv", "kom", Komi;
-	"kw", "cor", Cornish;
-	"ky", "kir", Kyrgyz;
-	"la", "lat", Latin;
-	"lb", "ltz", Luxembourgish;
-	"lg", "lug", Ganda;
-	"li", "lim", Limburgish;
-	"ln", "lin", Lingala;
-	"lo", "lao", Lao;
-	"lt", "lit", Lithuanian;
-	"lu", "lub", LubaKatanga;
-	"lv", "lav", Latvian;
-	"mg", "mlg", Malagasy;
-	"mh", "mah", Marshallese;
-	"mi", "mri", Maori;
-	"mk", "mkd", Macedonian;
-	"ml", "mal", Malayalam;
-	"mn", "mon", Mongolian;
-	"mr", "mar", Marathi;
-	"ms", "msa", Malay;
-	"mt", "mlt", Maltese;
-	"my", "mya", Burmese;
-	"na", "nau", Nauru;
-	"nb", "nob", NorwegianBokmal;
-	"nd", "nde", NorthNdebele;
-	"ne", "nep", Nepali;
-	"ng", "ndo", Ndonga;
-	"nl", "nld", Dutch;
-	"nn", "nno", NorwegianNynorsk;
-	"no", "nor", Norwegian;
-	"nr", "nbl", SouthNdebele;
-	"nv", "nav", Navaho;
-	"ny", "nya", Chichewa;
-	"oc", "oci", Occitan;
-	"oj", "oji", Ojibwa;
-	"om", "orm", Oromo;
-	"or", "ori", Oriya;
-	"os", "oss", Ossetian;
-	"pa", "pan", Punjabi;
-	"pi", "pli", Pali;
-	"pl", "pol", Polish;
-	"ps", "pus", Pashto;
-	"pt", "por", Portuguese;
-	"qu", "que", Quechua;
-	"rm", "roh", Romansh;
-	"rn", "run", Rundi;
-	"ro", "ron", Romanian;
-	"ru", "rus", Russian;
-	"rw", "kin", Kinyarwanda;
-	"sa", "san", Sanskrit;
-	"sc", "srd", Sardinian;
-	"sd", "snd", Sindhi;
-	"se", "sme", Sami;
-	"sg", "sag", Sango;
-	"si", "sin", Sinhala;
-	"sk", "slk", Slovak;
-	"sl", "slv", Slovenian;
-	"sm", "smo", Samoan;
-	"sn", "sna", Shona;
-	"so", "som", Somali;
-	"sq", "sqi", Albanian;
-	"sr", "srp", Serbian;
-	"ss", "ssw", Swati;
-	"st", "sot", Sotho;
-	"su", "sun", Sundanese;
-	"sv", "swe", Swedish;
-	"sw", "swa", Swahili;
-	"ta", "tam", Tamil;
-	"te", "tel", Telugu;
-	"tg", "tgk", Tajik;
-	"th", "tha", Thai;
-	"ti", "tir", Tigrinya;
-	"tk", "tuk", Turkmen;
-	"tl", "tgl", Tagalog;
-	"tn", "tsn", Tswana;
-	"to", "ton", Tongan;
-	"tok", "tok", TokiPona; // technically invalid ISO 693-1 code
-	"tr", "tur", Turkish;
-	"ts", "tso", Tsonga;
-	"tt", "tat", Tatar;
-	"tw", "twi", Twi;
-	"ty", "tah", Tahitian;
-	"ug", "uig", Uyghur;
-	"uk", "ukr", Ukrainian;
-	"ur", "urd", Urdu;
-	"uz", "uzb", Uzbek;
-	"ve", "ven", Venda;
-	"vi", "vie", Vietnamese;
-	"vo", "vol", Volapuk;
-	"wa", "wln", Walloon;
-	"wo", "wol", Wolof;
-	"xh", "xho", Xhosa;
-	"yi", "yid", Yiddish;
-	"yo", "yor", Yoruba;
-	"za", "zha", Zhuang;
-	"zh", "zho", Chinese;
-	"zu", "zul", Zulu;
+	"aa", "aar", Afar, [Latin];
+	"ab", "abk", Abkhazian, [Cyrillic];
+	"ae", "ave", Avestan, [Latin];
+	"af", "afr", Afrikaans, [Latin];
+	"ak", "aka", Akan, [Latin];
+	"am", "amh", Amharic, [Ethiopic];
+	"an", "arg", Aragonese, [Latin];
+	"ar", "ara", Arabic, [Arabic];
+	"as", "asm", Assamese, [Bengali];
+	"av", "ava", Avaric, [Cyrillic];
+	"ay", "aym", Aymara, [Latin];
+	"az", "aze", Azerbaijani, [Latin, Cyrillic, Arabic];
+	"ba", "bak", Bashkir, [Cyrillic];
+	"be", "bel", Belarusian, [Cyrillic];
+	"bg", "bul", Bulgarian, [Cyrillic];
+	"bi", "bis", Bislama, [Latin];
+	"bm", "bam", Bambara, [Latin];
+	"bn", "ben", Bengali, [Bengali];
+	"bo", "bod", Tibetan, [Tibetan];
+	"br", "bre", Breton, [Latin];
+	"bs", "bos", Bosnian, [Latin, Cyrillic];
+	"ca", "cat", Catalan, [Latin];
+	"ce", "che", Chechen, [Cyrillic];
+	"ch", "cha", Chamorro, [Latin];
+	"co", "cos", Corsican, [Latin];
+	"cr", "cre", Cree, [CanadianSyllabics, Latin];
+	"cs", "ces", Czech, [Latin];
+	"cu", "chu", ChurchSlavonic, [Cyrillic];
+	"cv", "chv", Chuvash, [Cyrillic];
+	"cy", "cym", Welsh, [Latin];
+	"da", "dan", Danish, [Latin];
+	"de", "deu", German, [Latin];
+	"dv", "div", Dhivehi, [Thaana];
+	"dz", "dzo", Dzongkha, [Tibetan];
+	"ee", "ewe", Ewe, [Latin];
+	"el", "ell", Greek, [Greek];
+	"en", "eng", English, [Latin];
+	"eo", "epo", Esperanto, [Latin];
+	"es", "spa", Spanish, [Latin];
+	"et", "est", Estonian, [Latin];
+	"eu", "eus", Basque, [Latin];
+	"fa", "fas", Persian, [Arabic];
+	"ff", "ful", Fulah, [Latin];
+	"fi", "fin", Finnish, [Latin];
+	"fj", "fij", Fijian, [Latin];
+	"fo", "fao", Faroese, [Latin];
+	"fr", "fra", French, [Latin];
+	"fy", "fry", Frisian, [Latin];
+	"ga", "gle", Irish, [Latin];
+	"gd", "gla", ScottishGaelic, [Latin];
+	"gl", "glg", Galician, [Latin];
+	"gn", "grn", Guarani, [Latin];
+	"gu", "guj", Gujarati, [Gujarati];
+	"gv", "glv", Manx, [Latin];
+	"ha", "hau", Hausa, [Latin, Arabic];
+	"he", "heb", Hebrew, [Hebrew];
+	"hi", "hin", Hindi, [Devanagari];
+	"ho", "hmo", HiriMotu, [Latin];
+	"hr", "hrv", Croatian, [Latin];
+	"ht", "hat", HaitianCreole, [Latin];
+	"hu", "hun", Hungarian, [Latin];
+	"hy", "hye", Armenian, [Armenian];
+	"hz", "her", Herero, [Latin];
+	"ia", "ina", Interlingua, [Latin];
+	"id", "ind", Indonesian, [Latin];
+	"ie", "ile", Interlingue, [Latin];
+	"ig", "ibo", Igbo, [Latin];
+	"ii", "iii", Yi, [Yi];
+	"ik", "ipk", Inupiaq, [Latin];
+	"io", "ido", Ido, [Latin];
+	"is", "isl", Icelandic, [Latin];
+	"it", "ita", Italian, [Latin];
+	"iu", "iku", Inuktitut, [CanadianSyllabics, Latin];
+	"ja", "jpn", Japanese, [Kana, Han];
+	"jbo", "jbo", Lojban, [Latin]; // technically invalid ISO 693-1 code
+	"jv", "jav", Javanese, [Latin];
+	"ka", "kat", Georgian, [Georgian];
+	"kg", "kon", Kongo, [Latin];
+	"ki", "kik", Kikuyu, [Latin];
+	"kj", "kua", Kwanyama, [Latin];
+	"kk", "kaz", Kazakh, [Cyrillic, Latin];
+	"kl", "kal", Kalaallisut, [Latin];
+	"km", "khm", Khmer, [Khmer];
+	"kn", "kan", Kannada, [Kannada];
+	"ko", "kor", Korean, [Hangul, Han];
+	"kr", "kau", Kanuri, [Latin];
+	"ks", "kas", Kashmiri, [Arabic, Devanagari];
+	"ku", "kur", Kurdish, [Latin, Arabic];
+	"kv", "kom", Komi, [Cyrillic];
+	"kw", "cor", Cornish, [Latin];
+	"ky", "kir", Kyrgyz, [Cyrillic];
+	"la", "lat", Latin, [Latin];
+	"lb", "ltz", Luxembourgish, [Latin];
+	"lg", "lug", Ganda, [Latin];
+	"li", "lim", Limburgish, [Latin];
+	"ln", "lin", Lingala, [Latin];
+	"lo", "lao", Lao, [Lao];
+	"lt", "lit", Lithuanian, [Latin];
+	"lu", "lub", LubaKatanga, [Latin];
+	"lv", "lav", Latvian, [Latin];
+	"mg", "mlg", Malagasy, [Latin];
+	"mh", "mah", Marshallese, [Latin];
+	"mi", "mri", Maori, [Latin];
+	"mk", "mkd", Macedonian, [Cyrillic];
+	"ml", "mal", Malayalam, [Malayalam];
+	"mn", "mon", Mongolian, [Cyrillic, Mongolian];
+	"mr", "mar", Marathi, [Devanagari];
+	"ms", "msa", Malay, [Latin];
+	"mt", "mlt", Maltese, [Latin];
+	"my", "mya", Burmese, [Myanmar];
+	"na", "nau", Nauru, [Latin];
+	"nb", "nob", NorwegianBokmal, [Latin];
+	"nd", "nde", NorthNdebele, [Latin];
+	"ne", "nep", Nepali, [Devanagari];
+	"ng", "ndo", Ndonga, [Latin];
+	"nl", "nld", Dutch, [Latin];
+	"nn", "nno", NorwegianNynorsk, [Latin];
+	"no", "nor", Norwegian, [Latin];
+	"nr", "nbl", SouthNdebele, [Latin];
+	"nv", "nav", Navaho, [Latin];
+	"ny", "nya", Chichewa, [Latin];
+	"oc", "oci", Occitan, [Latin];
+	"oj", "oji", Ojibwa, [Latin, CanadianSyllabics];
+	"om", "orm", Oromo, [Latin];
+	"or", "ori", Oriya, [Oriya];
+	"os", "oss", Ossetian, [Cyrillic];
+	"pa", "pan", Punjabi, [Gurmukhi, Arabic];
+	"pi", "pli", Pali, [Latin, Devanagari, Sinhala, Thai, Myanmar];
+	"pl", "pol", Polish, [Latin];
+	"ps", "pus", Pashto, [Arabic];
+	"pt", "por", Portuguese, [Latin];
+	"qu", "que", Quechua, [Latin];
+	"rm", "roh", Romansh, [Latin];
+	"rn", "run", Rundi, [Latin];
+	"ro", "ron", Romanian, [Latin];
+	"ru", "rus", Russian, [Cyrillic];
+	"rw", "kin", Kinyarwanda, [Latin];
+	"sa", "san", Sanskrit, [Devanagari];
+	"sc", "srd", Sardinian, [Latin];
+	"sd", "snd", Sindhi, [Arabic, Devanagari];
+	"se", "sme", Sami, [Latin];
+	"sg", "sag", Sango, [Latin];
+	"si", "sin", Sinhala, [Sinhala];
+	"sk", "slk", Slovak, [Latin];
+	"sl", "slv", Slovenian, [Latin];
+	"sm", "smo", Samoan, [Latin];
+	"sn", "sna", Shona, [Latin];
+	"so", "som", Somali, [Latin];
+	"sq", "sqi", Albanian, [Latin];
+	"sr", "srp", Serbian, [Cyrillic, Latin];
+	"ss", "ssw", Swati, [Latin];
+	"st", "sot", Sotho, [Latin];
+	"su", "sun", Sundanese, [Latin];
+	"sv", "swe", Swedish, [Latin];
+	"sw", "swa", Swahili, [Latin];
+	"ta", "tam", Tamil, [Tamil];
+	"te", "tel", Telugu, [Telugu];
+	"tg", "tgk", Tajik, [Cyrillic];
+	"th", "tha", Thai, [Thai];
+	"ti", "tir", Tigrinya, [Ethiopic];
+	"tk", "tuk", Turkmen, [Latin];
+	"tl", "tgl", Tagalog, [Latin];
+	"tn", "tsn", Tswana, [Latin];
+	"to", "ton", Tongan, [Latin];
+	"tok", "tok", TokiPona, [Latin]; // technically invalid ISO 693-1 code
+	"tr", "tur", Turkish, [Latin];
+	"ts", "tso", Tsonga, [Latin];
+	"tt", "tat", Tatar, [Cyrillic];
+	"tw", "twi", Twi, [Latin];
+	"ty", "tah", Tahitian, [Latin];
+	"ug", "uig", Uyghur, [Arabic];
+	"uk", "ukr", Ukrainian, [Cyrillic];
+	"ur", "urd", Urdu, [Arabic];
+	"uz", "uzb", Uzbek, [Latin, Cyrillic];
+	"ve", "ven", Venda, [Latin];
+	"vi", "vie", Vietnamese, [Latin];
+	"vo", "vol", Volapuk, [Latin];
+	"wa", "wln", Walloon, [Latin];
+	"wo", "wol", Wolof, [Latin];
+	"xh", "xho", Xhosa, [Latin];
+	"yi", "yid", Yiddish, [Hebrew];
+	"yo", "yor", Yoruba, [Latin];
+	"za", "zha", Zhuang, [Latin];
+	"zh", "zho", Chinese, [Han];
+	"zu", "zul", Zulu, [Latin];
 }
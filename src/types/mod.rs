@@ -1,8 +1,13 @@
+pub mod advisory;
+pub mod assistrole;
 pub mod color;
 pub mod date;
 pub mod duration;
 pub mod genre;
 pub mod isrc;
 pub mod language;
+pub mod releasetype;
+pub mod script;
 pub mod upc;
 pub mod urlset;
+pub mod versionkind;
@@ -0,0 +1,67 @@
+macro_rules! define_release_types {
+	(
+		$(
+			$text:expr, $label:expr, $name:ident ;
+		)*
+	) => {
+		#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+		pub enum ReleaseType {
+			$($name),*
+		}
+		impl ReleaseType {
+			pub const ALL: &[ReleaseType] = &[$(ReleaseType::$name),*];
+			// also what goes in the RELEASETYPE tags, musicbrainz-style
+			pub fn to_string(self) -> &'static str {
+				match self {
+					$(ReleaseType::$name => $text),*
+				}
+			}
+			pub fn label(self) -> &'static str {
+				match self {
+					$(ReleaseType::$name => $label),*
+				}
+			}
+			pub fn from(s: &str) -> ReleaseType {
+				match s {
+					$(
+						$text => ReleaseType::$name,
+					)*
+					_ => panic!("Unrecognized release type \"{}\"", s),
+				}
+			}
+		}
+	};
+}
+
+define_release_types!(
+	"album", "Album", Album;
+	"ep", "EP", Ep;
+	"single", "Single", Single;
+	"compilation", "Compilation", Compilation;
+	"mixtape", "Mixtape", Mixtape;
+	"live", "Live album", Live;
+	"dj-mix", "DJ mix", DjMix;
+	"remix", "Remix", Remix;
+);
+
+impl ReleaseType {
+	// remixes go in discog.remixes
+	pub fn allowed_for_albums(self) -> bool {
+		self != ReleaseType::Remix
+	}
+	// what a song can be when it isn't on an album
+	pub fn allowed_for_standalone_songs(self) -> bool {
+		matches!(
+			self,
+			ReleaseType::Remix | ReleaseType::DjMix | ReleaseType::Live | ReleaseType::Mixtape
+		)
+	}
+	// collections of other things get to be playlists
+	pub fn og_type(self) -> &'static str {
+		match self {
+			ReleaseType::Compilation | ReleaseType::Mixtape => "music.playlist",
+			ReleaseType::Remix | ReleaseType::DjMix => "music.song",
+			_ => "music.album"
+		}
+	}
+}
@@ -0,0 +1,110 @@
+// writing systems, just enough to sanity check lyric language tags
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Script {
+	Latin,
+	Greek,
+	Cyrillic,
+	Armenian,
+	Hebrew,
+	Arabic,
+	Thaana,
+	Devanagari,
+	Bengali,
+	Gurmukhi,
+	Gujarati,
+	Oriya,
+	Tamil,
+	Telugu,
+	Kannada,
+	Malayalam,
+	Sinhala,
+	Thai,
+	Lao,
+	Tibetan,
+	Myanmar,
+	Georgian,
+	Hangul,
+	Ethiopic,
+	CanadianSyllabics,
+	Khmer,
+	Mongolian,
+	Kana,
+	Han,
+	Yi
+}
+
+impl Script {
+	// None for anything that isn't a letter, or is in a script we don't track
+	pub fn of(character: char) -> Option<Script> {
+		if !character.is_alphabetic() {
+			return None;
+		}
+		let script = match u32::from(character) {
+			0x0041..=0x005A
+			| 0x0061..=0x007A
+			| 0x00AA
+			| 0x00BA
+			| 0x00C0..=0x00D6
+			| 0x00D8..=0x00F6
+			| 0x00F8..=0x02AF
+			| 0x1E00..=0x1EFF
+			| 0x2C60..=0x2C7F
+			| 0xA720..=0xA7FF
+			| 0xFF21..=0xFF3A
+			| 0xFF41..=0xFF5A => Script::Latin,
+			0x0370..=0x03FF | 0x1F00..=0x1FFF => Script::Greek,
+			0x0400..=0x052F | 0x1C80..=0x1C8F | 0x2DE0..=0x2DFF | 0xA640..=0xA69F => {
+				Script::Cyrillic
+			}
+			0x0530..=0x058F => Script::Armenian,
+			0x0590..=0x05FF | 0xFB1D..=0xFB4F => Script::Hebrew,
+			0x0600..=0x06FF
+			| 0x0750..=0x077F
+			| 0x08A0..=0x08FF
+			| 0xFB50..=0xFDFF
+			| 0xFE70..=0xFEFF => Script::Arabic,
+			0x0780..=0x07BF => Script::Thaana,
+			0x0900..=0x097F | 0xA8E0..=0xA8FF => Script::Devanagari,
+			0x0980..=0x09FF => Script::Bengali,
+			0x0A00..=0x0A7F => Script::Gurmukhi,
+			0x0A80..=0x0AFF => Script::Gujarati,
+			0x0B00..=0x0B7F => Script::Oriya,
+			0x0B80..=0x0BFF => Script::Tamil,
+			0x0C00..=0x0C7F => Script::Telugu,
+			0x0C80..=0x0CFF => Script::Kannada,
+			0x0D00..=0x0D7F => Script::Malayalam,
+			0x0D80..=0x0DFF => Script::Sinhala,
+			0x0E00..=0x0E7F => Script::Thai,
+			0x0E80..=0x0EFF => Script::Lao,
+			0x0F00..=0x0FFF => Script::Tibetan,
+			0x1000..=0x109F => Script::Myanmar,
+			0x10A0..=0x10FF | 0x1C90..=0x1CBF => Script::Georgian,
+			0x1100..=0x11FF | 0x3130..=0x318F | 0xA960..=0xA97F | 0xAC00..=0xD7FF => Script::Hangul,
+			0x1200..=0x139F | 0x2D80..=0x2DDF => Script::Ethiopic,
+			0x1400..=0x167F | 0x18B0..=0x18FF => Script::CanadianSyllabics,
+			0x1780..=0x17FF | 0x19E0..=0x19FF => Script::Khmer,
+			0x1800..=0x18AF => Script::Mongolian,
+			0x3040..=0x30FF | 0x31F0..=0x31FF | 0xFF66..=0xFF9F => Script::Kana,
+			0x2E80..=0x2FDF
+			| 0x3005..=0x3007
+			| 0x3400..=0x4DBF
+			| 0x4E00..=0x9FFF
+			| 0xF900..=0xFAFF
+			| 0x20000..=0x2FFFF => Script::Han,
+			0xA000..=0xA4CF => Script::Yi,
+			_ => return None
+		};
+		Some(script)
+	}
+	// every script that shows up in the text, in order of first appearance
+	pub fn all_in(text: &str) -> Vec<Script> {
+		let mut scripts = Vec::new();
+		for script in text.chars().filter_map(Script::of) {
+			if !scripts.contains(&script) {
+				scripts.push(script);
+			}
+		}
+		scripts
+	}
+}
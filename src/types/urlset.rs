@@ -26,6 +26,7 @@ macro_rules! define_urlset {
 			$( $field: Option<String>, )*
 		}
 		impl UrlSet {
+			pub const PLATFORMS: &[&str] = &[$($label),*];
 			pub fn empty() -> UrlSet {
 				UrlSet {
 					$( $field: None, )*
@@ -50,13 +51,7 @@ macro_rules! define_urlset {
 						s
 					})
 				}
-				let obj = crate::globals::map_with_only_these_keys(
-					val,
-					"UrlSet",
-					&[
-						$( $label, )*
-					]
-				);
+				let obj = crate::globals::map_with_only_these_keys(val, "UrlSet", UrlSet::PLATFORMS);
 				UrlSet {
 					$( $field: get_str(&obj, $label, $required_substring), )*
 				}
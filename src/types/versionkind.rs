@@ -0,0 +1,42 @@
+macro_rules! define_version_kinds {
+	(
+		$(
+			$text:expr, $label:expr, $name:ident ;
+		)*
+	) => {
+		#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+		pub enum VersionKind {
+			$($name),*
+		}
+		impl VersionKind {
+			pub const ALL: &[VersionKind] = &[$(VersionKind::$name),*];
+			pub fn to_string(self) -> &'static str {
+				match self {
+					$(VersionKind::$name => $text),*
+				}
+			}
+			// also what goes in the VERSION tags
+			pub fn label(self) -> &'static str {
+				match self {
+					$(VersionKind::$name => $label),*
+				}
+			}
+			pub fn from(s: &str) -> VersionKind {
+				match s {
+					$(
+						$text => VersionKind::$name,
+					)*
+					_ => panic!("Unrecognized version kind \"{}\"", s),
+				}
+			}
+		}
+	};
+}
+
+define_version_kinds!(
+	"extended", "Extended mix", Extended;
+	"instrumental", "Instrumental", Instrumental;
+	"radio-edit", "Radio edit", RadioEdit;
+	"remix", "Remix", Remix;
+	"acoustic", "Acoustic", Acoustic;
+);